@@ -0,0 +1,29 @@
+// Build script of cfg_boost enabling features of recent toolchains without raising the minimum Rust version.
+
+use std::{env, process::Command};
+
+// Contants
+const RUSTC_KEY : &str = "RUSTC";                                   // Compiler set by Cargo
+const SPAN_LOCATIONS_CFG : &str = "span_locations";                 // Cfg of Span::file, Span::line and Span::column
+const SPAN_LOCATIONS_MINOR : u32 = 88;                              // Rust 1.88 stabilized span locations
+
+fn main() {
+
+    // 1. Only rerun when build script changes. Cargo already reruns it for another compiler.
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg({})", SPAN_LOCATIONS_CFG);
+
+    // 2. Minor version of `rustc 1.xx.y`
+    let rustc = env::var(RUSTC_KEY).unwrap_or(String::from("rustc"));
+    let minor = Command::new(rustc).arg("--version").output().ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .and_then(|version| version.split('.').nth(1).and_then(|minor| minor.parse::<u32>().ok()));
+
+    // 3. Enable span locations of expansion reports and cfg_label!
+    if let Some(minor) = minor {
+        if minor >= SPAN_LOCATIONS_MINOR {
+            println!("cargo:rustc-cfg={}", SPAN_LOCATIONS_CFG);
+        }
+    }
+
+}
@@ -0,0 +1,284 @@
+//! Build script helper of [cfg_boost](https://crates.io/crates/cfg_boost).
+//!
+//! Since Rust 1.80, custom cfgs used by [custom predicates](https://github.com/NickelAngeStudio/cfg_boost#configuration) and aliases trigger
+//! `unexpected_cfgs` warnings unless they are declared with `cargo::rustc-check-cfg`. This crate reads the same cfg_boost configuration
+//! in `build.rs` and declares them, so that aliases and custom predicates stay the single source of truth.
+//!
+//! ```toml
+//! [build-dependencies]
+//! cfg_boost_build = "1.0.0"
+//! ```
+//! Then in `fn main()` of `build.rs` :
+//! ```no_run
+//! cfg_boost_build::emit_check_cfg();
+//! ```
+//!
+//! With a custom predicate `c1 = "custom1 = \"{}\""` and an alias `fish = "foo:c1 | bar:_"`, `build.rs` prints :
+//! ```text
+//! cargo::rustc-check-cfg=cfg(bar)
+//! cargo::rustc-check-cfg=cfg(custom1, values(any()))
+//! ```
+//! Values of a custom predicate are restricted to the labels of aliases and `values` table when `values.c1 = "foo, baz"` is set.
+//!
+//! Configuration is loaded by [cfg_boost_parser] like cfg_boost macros. A malformed configuration file or a missing file given by
+//! `CFG_BOOST_CONFIG` fails the build script with the same message.
+
+use std::{collections::{BTreeMap, BTreeSet}, env, path::Path};
+
+use cfg_boost_parser::{tokenize, ConfigError, Delimiter, Settings, Token, CFG_ANY, CFG_ALL, CFG_NOT, CARGO_MANIFEST_DIR, CFG_BOOST_CONFIG, ENV_KEY_ALIAS, ENV_KEY_PREDICATE, ENV_KEY_VALUES, PREDICATE_PLACEHOLDER};
+
+#[cfg(test)]
+#[path = "../../tests/unit/build.rs"]
+mod unit_tests; // Unit tests located in tests folder
+
+// Contants
+const FILE_KEY_ALIAS : &str = "aliases";                            // Configuration file aliases table
+const FILE_KEY_PREDICATE : &str = "predicates";                     // Configuration file predicates table
+const FILE_KEY_VALUES : &str = "values";                            // Configuration file predicate values table
+const WILDCARD_PREDICATE : &str = "_";                              // Predicate of a cfg name without value
+const VALUES_SEPARATOR : char = ',';                                // Predicate values separator
+const LEAF_SEPARATORS : [char; 6] = ['|', '&', '!', '(', ')', ','];   // Operators between alias leaves
+const PREDICATE_SEPARATOR : char = ':';                             // Separator between label and predicate
+const CHECK_CFG_PREFIX : &str = "cargo::rustc-check-cfg=";          // Cargo instruction declaring expected cfg
+const RERUN_FILE_PREFIX : &str = "cargo::rerun-if-changed=";        // Cargo instruction to rerun build script when file changes
+const RERUN_ENV_PREFIX : &str = "cargo::rerun-if-env-changed=";     // Cargo instruction to rerun build script when env changes
+
+/// Cargo configuration files whose `[env]` can add aliases and predicates, looked up in folders containing the crate.
+const CARGO_CONFIG_FILES : [&str; 2] = [".cargo/config.toml", ".cargo/config"];
+
+/// Cfg operators of predicates
+const CFG_OPERATORS : [&str; 3] = [CFG_ANY, CFG_ALL, CFG_NOT];
+
+/// Cfg names and keys already known by rustc, never declared.
+const WELL_KNOWN_NAMES : [&str; 14] = ["clippy", "contract_checks", "debug_assertions", "doc", "doctest", "miri", "overflow_checks", "proc_macro", "rustfmt",
+    "target_thread_local", "test", "ub_checks", "unix", "windows"];
+const WELL_KNOWN_KEYS : [&str; 5] = ["feature", "panic", "relocation_model", "sanitize", "fmt_debug"];
+const WELL_KNOWN_KEY_PREFIX : &str = "target_";
+
+/// Print `cargo::rustc-check-cfg` lines of custom predicates and aliases in cfg_boost configuration.
+///
+/// Configuration files read and existing `.cargo/config.toml` are tracked with `cargo::rerun-if-changed`, environment variables of aliases
+/// and predicates with `cargo::rerun-if-env-changed`. Must be called from `build.rs`.
+///
+/// Any `cargo::rerun-if-changed` line turns off the Cargo default of rerunning `build.rs` when any file of the package changes.
+/// A `build.rs` reading other files must print its own `cargo::rerun-if-changed` lines. Creating a `cfg_boost.toml` isn't detected
+/// until `build.rs` or `Cargo.toml` changes.
+///
+/// Panic(s)
+/// Panic with [ConfigError] message if configuration is malformed or if file given by `CFG_BOOST_CONFIG` is missing.
+pub fn emit_check_cfg() {
+
+    let settings = match Settings::load() {
+        Ok(settings) => settings,
+        Err(err) => panic!("{}", err),
+    };
+
+    let tables = CfgTables::new(&settings, env::vars());
+
+    // 1. Rerun build script when configuration files change. Only existing files are tracked since Cargo reruns on every build for missing ones.
+    for file in settings.files() {
+        println!("{}{}", RERUN_FILE_PREFIX, file.display());
+    }
+
+    // 2. Rerun build script when `[env]` of Cargo configuration files adds variables.
+    if let Ok(manifest_dir) = env::var(CARGO_MANIFEST_DIR) {
+        for file in Path::new(&manifest_dir).ancestors().flat_map(|dir| CARGO_CONFIG_FILES.map(|name| dir.join(name))).filter(|file| file.is_file()) {
+            println!("{}{}", RERUN_FILE_PREFIX, file.display());
+        }
+    }
+
+    // 3. Rerun build script when environment variables read by tables change.
+    println!("{}{}", RERUN_ENV_PREFIX, CFG_BOOST_CONFIG);
+    for key in &tables.env_keys {
+        println!("{}{}", RERUN_ENV_PREFIX, key);
+    }
+
+    // 4. Declare expected cfg
+    for line in tables.check_cfg() {
+        println!("{}", line);
+    }
+
+}
+
+/// Returns `cargo::rustc-check-cfg` lines of custom predicates and aliases in cfg_boost configuration, without printing them.
+///
+/// Error(s)
+/// Returns Err([ConfigError]) if configuration is malformed or if file given by `CFG_BOOST_CONFIG` is missing.
+pub fn check_cfg() -> Result<Vec<String>, ConfigError> {
+    Ok(CfgTables::new(&Settings::load()?, env::vars()).check_cfg())
+}
+
+/// Aliases, custom predicates and predicate values of cfg_boost configuration.
+#[derive(Default)]
+struct CfgTables {
+    aliases : BTreeMap<String, String>,         // Aliases and their content
+    predicates : BTreeMap<String, String>,      // Custom predicates and their template
+    values : BTreeMap<String, String>,          // Predicate values separated by `,`
+    env_keys : BTreeSet<String>,                // Environment variables of aliases, predicates and values
+}
+
+impl CfgTables {
+    /// Create tables from configuration settings, completed by environment variables.
+    fn new(settings : &Settings, vars : impl Iterator<Item = (String, String)>) -> CfgTables {
+
+        let mut tables = CfgTables::default();
+
+        // 1. Configuration files tables
+        for (map, table_name) in [(&mut tables.aliases, FILE_KEY_ALIAS), (&mut tables.predicates, FILE_KEY_PREDICATE), (&mut tables.values, FILE_KEY_VALUES)] {
+            for (key, value) in settings.table(table_name) {
+                if let Some(value) = value.as_str() {
+                    map.insert(String::from(key), String::from(value));
+                }
+            }
+        }
+
+        // 2. Environment variables, unless set in configuration files.
+        for (key, value) in vars {
+            let entry = match (key.strip_prefix(ENV_KEY_PREDICATE), key.strip_prefix(ENV_KEY_VALUES), key.strip_prefix(ENV_KEY_ALIAS)) {
+                (Some(name), _, _) => tables.predicates.entry(String::from(name)),
+                (_, Some(name), _) => tables.values.entry(String::from(name)),
+                (_, _, Some(name)) => tables.aliases.entry(String::from(name)),
+                _ => continue,
+            };
+            entry.or_insert(value);
+            tables.env_keys.insert(key);
+        }
+
+        tables
+
+    }
+
+    /// Generate `cargo::rustc-check-cfg` lines of custom predicates and aliases.
+    fn check_cfg(&self) -> Vec<String> {
+
+        // Expected cfg names with their values. None means any value.
+        let mut expected : BTreeMap<String, Option<BTreeSet<String>>> = BTreeMap::new();
+
+        // 1. Labels used with each custom predicate in aliases and values table. Bare cfg names of `_` predicate.
+        let mut labels : BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for alias in self.aliases.values() {
+            for leaf in alias.split(|c : char| LEAF_SEPARATORS.contains(&c) || c.is_whitespace()).filter(|leaf| !leaf.is_empty()) {
+                if let Some((label, predicate)) = leaf.split_once(PREDICATE_SEPARATOR) {
+                    match self.predicates.get_key_value(predicate) {
+                        Some((predicate, _)) => { labels.entry(predicate.as_str()).or_default().insert(String::from(label)); },
+                        None if predicate.eq(WILDCARD_PREDICATE) => Self::expect_name(&mut expected, label),
+                        None => {},     // Predefined predicates are well-known
+                    }
+                }
+            }
+        }
+        for (predicate, values) in &self.values {
+            if let Some((predicate, _)) = self.predicates.get_key_value(predicate) {
+                labels.entry(predicate.as_str()).or_default().extend(values.split(VALUES_SEPARATOR).map(|v| String::from(v.trim())).filter(|v| !v.is_empty()));
+            }
+        }
+
+        // 2. Cfg of custom predicates templates. Values are restricted only if set in values table. Malformed templates are reported by macros.
+        for (predicate, template) in &self.predicates {
+            let labels = labels.remove(predicate.as_str()).unwrap_or_default();
+            let restricted = self.values.contains_key(predicate);
+
+            for cfg in template_cfg(&tokenize(template).unwrap_or_default()) {
+                match cfg {
+                    // key = "value"
+                    TemplateCfg::KeyValue(key, value) if !is_well_known_key(&key) => {
+                        let values = if value.contains(PREDICATE_PLACEHOLDER) {
+                            if restricted { Some(labels.iter().map(|label| value.replace(PREDICATE_PLACEHOLDER, label)).collect()) } else { None }
+                        } else {
+                            Some(BTreeSet::from([value]))
+                        };
+                        Self::expect_values(&mut expected, &key, values);
+                    },
+                    // Bare name
+                    TemplateCfg::Name(name) => {
+                        if name.contains(PREDICATE_PLACEHOLDER) {
+                            labels.iter().for_each(|label| Self::expect_name(&mut expected, &name.replace(PREDICATE_PLACEHOLDER, label)));
+                        } else {
+                            Self::expect_name(&mut expected, &name);
+                        }
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        // 3. Write lines
+        expected.iter().map(|(name, values)| match values {
+            None => format!("{}cfg({}, values(any()))", CHECK_CFG_PREFIX, name),
+            Some(values) if values.is_empty() => format!("{}cfg({})", CHECK_CFG_PREFIX, name),
+            Some(values) => format!("{}cfg({}, values({}))", CHECK_CFG_PREFIX, name, values.iter().map(|v| format!("\"{}\"", v)).collect::<Vec<String>>().join(", ")),
+        }).collect()
+
+    }
+
+    /// Expect a cfg name without value, unless well-known.
+    fn expect_name(expected : &mut BTreeMap<String, Option<BTreeSet<String>>>, name : &str) {
+        if !WELL_KNOWN_NAMES.contains(&name) {
+            expected.entry(String::from(name)).or_insert(Some(BTreeSet::new()));
+        }
+    }
+
+    /// Expect values of a cfg key. None means any value.
+    fn expect_values(expected : &mut BTreeMap<String, Option<BTreeSet<String>>>, key : &str, values : Option<BTreeSet<String>>) {
+        let entry = expected.entry(String::from(key)).or_insert(Some(BTreeSet::new()));
+        match (entry.as_mut(), values) {
+            (Some(expected), Some(values)) => expected.extend(values),
+            _ => *entry = None,
+        }
+    }
+}
+
+/// Cfg of a predicate template, which may contain placeholder.
+#[derive(Debug, PartialEq)]
+enum TemplateCfg {
+    /// Cfg name like `custom_flag` or `name_{}`.
+    Name(String),
+
+    /// Cfg key and unquoted value like `custom1 = "{}"`.
+    KeyValue(String, String),
+}
+
+/// Collect cfg of predicate template tokens like `all(custom1 = "{}", unix)`, without operators.
+fn template_cfg(tokens : &[Token]) -> Vec<TemplateCfg> {
+
+    let mut cfg : Vec<TemplateCfg> = Vec::new();
+    let mut index = 0;
+
+    while index < tokens.len() {
+        // 1. Name made of identifiers and placeholders, like `name_{}`.
+        let mut name = String::new();
+        while let Some(token) = tokens.get(index) {
+            match token {
+                Token::Ident(ident) => name.push_str(ident),
+                Token::Group(Delimiter::Brace, group) if group.is_empty() => name.push_str(PREDICATE_PLACEHOLDER),
+                _ => break,
+            }
+            index += 1;
+        }
+
+        // 2. Key and value, operator content or bare name.
+        match (tokens.get(index), tokens.get(index + 1)) {
+            (Some(Token::Punct('=')), Some(Token::Literal(value))) if !name.is_empty() => {
+                cfg.push(TemplateCfg::KeyValue(name, String::from(value.trim_matches('"'))));
+                index += 2;
+            },
+            (Some(Token::Group(Delimiter::Parenthesis, group)), _) => {
+                if !name.is_empty() && !CFG_OPERATORS.contains(&name.as_str()) {
+                    cfg.push(TemplateCfg::Name(name));
+                }
+                cfg.extend(template_cfg(group));
+                index += 1;
+            },
+            _ if !name.is_empty() => cfg.push(TemplateCfg::Name(name)),
+            _ => index += 1,    // Comma and other punctuations
+        }
+    }
+
+    cfg
+
+}
+
+/// Returns true if cfg key is known by rustc or Cargo.
+fn is_well_known_key(key : &str) -> bool {
+    WELL_KNOWN_KEYS.contains(&key) || key.starts_with(WELL_KNOWN_KEY_PREFIX)
+}
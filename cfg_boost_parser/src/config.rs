@@ -0,0 +1,312 @@
+// Loading of the cfg_boost configuration of the crate being compiled, shared by cfg_boost macros, build scripts and tools.
+
+use std::{env, fmt, fs, path::{Path, PathBuf}};
+
+use crate::{parser::{Resolver, Settings}, toml::{TomlDocument, TomlValue, KEY_SEPARATOR}};
+
+#[cfg(test)]
+#[path = "../../tests/unit/settings.rs"]
+mod unit_tests; // Unit tests located in tests folder
+
+// Contants
+pub const CARGO_MANIFEST_DIR : &str = "CARGO_MANIFEST_DIR";         // Cargo manifest dir key
+pub const CFG_BOOST_CONFIG : &str = "CFG_BOOST_CONFIG";             // Explicit configuration file path key, used without Cargo.
+const CARGO_MANIFEST_NAME : &str = "Cargo.toml";                    // Cargo manifest file name
+const CONFIG_FILE_NAME : &str = "cfg_boost.toml";                   // cfg_boost configuration file name
+const CONFIG_METADATA_TABLE : &str = "package.metadata.cfg_boost";  // cfg_boost table in Cargo.toml
+const CONFIG_WORKSPACE_TABLE : &str = "workspace.metadata.cfg_boost";   // cfg_boost table in workspace Cargo.toml
+const CARGO_WORKSPACE_TABLE : &str = "workspace";                   // Table that identify a workspace Cargo.toml
+const CARGO_PACKAGE_WORKSPACE_KEY : &str = "package.workspace";     // Explicit path to workspace root in Cargo.toml
+const CARGO_FEATURES_TABLE : &str = "features";                     // Features table in Cargo.toml
+const CARGO_DEPENDENCIES_TABLE : &str = "dependencies";             // Dependencies table in Cargo.toml, also under `target.*`
+const CARGO_OPTIONAL_KEY : &str = "optional";                       // Optional dependency key, which makes an implicit feature
+const CARGO_DEP_PREFIX : &str = "dep:";                             // Prefix of optional dependency in features, which removes its implicit feature
+const FILE_KEY_INHERIT : &str = "inherit";                          // Configuration file key to inherit workspace configuration
+const DOCSRS_KEY : &str = "cfg_boost_docsrs";                       // Key to force docs.rs tags on or off.
+const DOCSRS_CFG_KEY : &str = "cfg_boost_docsrs_cfg";               // Key of cfg name used by docs.rs tags.
+const DOCSRS_MODE_KEY : &str = "cfg_boost_docsrs_mode";             // Key of docs.rs tags mode.
+const FILE_KEY_DOCSRS : &str = "docsrs";                            // Configuration file key to force docs.rs tags on or off.
+const FILE_KEY_DOCSRS_CFG : &str = "docsrs_cfg";                    // Configuration file key of cfg name used by docs.rs tags.
+const FILE_KEY_DOCSRS_MODE : &str = "docsrs_mode";                  // Configuration file key of docs.rs tags mode.
+const DOCSRS_TABLE : &str = "package.metadata.docs.rs";             // docs.rs table in Cargo.toml
+const DOCSRS_ARGS : [&str; 2] = ["rustdoc-args", "cargo-args"];     // docs.rs table arguments searched for cfg
+const DOCSRS_DEFAULT_CFG : &str = "docsrs";                         // Default docs.rs cfg name
+const DOCSRS_CFG_ARG : &str = "--cfg";                              // Argument used to set a cfg
+const DOCSRS_MODE_AUTO_CFG : &str = "auto_cfg";                     // Mode that rely on doc_auto_cfg.
+
+/// Possible configuration loading errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// Configuration file is malformed at line.
+    Malformed(PathBuf, usize),
+
+    /// Configuration file given by `CFG_BOOST_CONFIG` is not found.
+    NotFound(PathBuf),
+}
+
+impl fmt::Display for ConfigError {
+    /// Write the error message.
+    fn fmt(&self, f : &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Malformed(path, line) => write!(f, "Configuration file `{}` is malformed at line {}!", path.display(), line),
+            ConfigError::NotFound(path) => write!(f, "Configuration file `{}` set with `CFG_BOOST_CONFIG` not found!", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Settings {
+    /// Load settings of the crate being compiled from `CARGO_MANIFEST_DIR` and `CFG_BOOST_CONFIG`, like cfg_boost macros.
+    ///
+    /// Error(s)
+    /// Returns Err([ConfigError]) if `cfg_boost.toml` is malformed or if file given by `CFG_BOOST_CONFIG` is missing or malformed.
+    pub fn load() -> Result<Settings, ConfigError> {
+        let manifest_dir = env::var(CARGO_MANIFEST_DIR).ok().map(PathBuf::from);
+        let config_file = env::var(CFG_BOOST_CONFIG).ok().map(PathBuf::from);
+
+        Settings::load_from(manifest_dir.as_deref(), config_file.as_deref())
+    }
+
+    /// Load settings of crate located in manifest_dir and explicit configuration file.
+    ///
+    /// Settings are fetched with the following precedence (first found is used) :
+    /// 1. File given by `CFG_BOOST_CONFIG` environment variable, for build systems other than Cargo.
+    /// 2. `cfg_boost.toml` in the crate root folder (`CARGO_MANIFEST_DIR`).
+    /// 3. `[package.metadata.cfg_boost]` table of the crate `Cargo.toml`.
+    /// 4. `[workspace.metadata.cfg_boost]` table of the workspace root `Cargo.toml`, only if crate set `inherit = true`.
+    /// 5. Environment variables, usually set in `[env]` of `.cargo/config.toml`.
+    /// 6. Built-in aliases, predicates and default values.
+    ///
+    /// Both are optional so crates built without Cargo can still be configured. Without manifest_dir, only 1, 5 and 6 are used.
+    /// Features of `Cargo.toml` are validated by `ft` predicate.
+    ///
+    /// All tables use the same keys :
+    /// ```toml
+    /// inherit = true
+    /// autodoc = true
+    /// availability_notes = false
+    /// doc_stub = false
+    /// docsrs = true
+    /// docsrs_cfg = "docsrs"
+    /// docsrs_mode = "doc_cfg"
+    /// release_modifier_behaviour = "panic"
+    /// check_values = true
+    /// check_features = true
+    ///
+    /// [aliases]
+    /// gpu_backend = "vulkan:ft | opengl:ft"
+    ///
+    /// [predicates]
+    /// c1 = "custom1 = \"{}\""
+    ///
+    /// [descriptions]
+    /// gpu_backend = "GPU backend"
+    ///
+    /// [values]
+    /// os = "myos, otheros"
+    /// ft = "injected_feature"
+    /// ```
+    ///
+    /// Error(s)
+    /// Returns Err([ConfigError]) if `cfg_boost.toml` is malformed or if config_file is missing or malformed.
+    pub fn load_from(manifest_dir : Option<&Path>, config_file : Option<&Path>) -> Result<Settings, ConfigError> {
+
+        let mut settings = Settings::default();
+
+        // Crate level configuration
+        let mut crate_table = TomlDocument::default();
+        let manifest = manifest_dir.and_then(|dir| read_toml(&dir.join(CARGO_MANIFEST_NAME)).ok().flatten());
+
+        // 1. [package.metadata.cfg_boost] from Cargo.toml
+        if let (Some(manifest), Some(manifest_dir)) = (&manifest, manifest_dir) {
+            crate_table.merge(&manifest.sub_document(CONFIG_METADATA_TABLE));
+            settings.track(&manifest_dir.join(CARGO_MANIFEST_NAME));
+        }
+
+        // 2. cfg_boost.toml has precedence over Cargo.toml
+        if let Some(manifest_dir) = manifest_dir {
+            if let Some(file) = read_toml(&manifest_dir.join(CONFIG_FILE_NAME))? {
+                crate_table.merge(&file);
+                settings.track(&manifest_dir.join(CONFIG_FILE_NAME));
+            }
+        }
+
+        // 3. Explicit configuration file has precedence over all
+        if let Some(path) = config_file {
+            match read_toml(path)? {
+                Some(file) => {
+                    crate_table.merge(&file);
+                    settings.track(path);
+                },
+                None => return Err(ConfigError::NotFound(path.to_path_buf())),
+            }
+        }
+
+        // 4. [workspace.metadata.cfg_boost] is merged under crate configuration if inherited.
+        if let (Some(true), Some(manifest_dir)) = (crate_table.get(FILE_KEY_INHERIT).and_then(|v| v.as_bool()), manifest_dir) {
+            if let Some((path, workspace)) = find_workspace_manifest(manifest_dir, manifest.as_ref()) {
+                settings.table.merge(&workspace.sub_document(CONFIG_WORKSPACE_TABLE));
+                settings.track(&path);
+            }
+        }
+        settings.table.merge(&crate_table);
+        settings.features = manifest.as_ref().map(read_features);
+        settings.manifest = manifest;
+
+        Ok(settings)
+
+    }
+
+    /// Get a configuration value from key like `aliases.desktop`, without environment variables.
+    pub fn get(&self, key : &str) -> Option<&TomlValue> {
+        self.table.get(key)
+    }
+
+    /// Get keys and values of a configuration table like `aliases`, without environment variables.
+    pub fn table(&self, table : &str) -> Vec<(&str, &TomlValue)> {
+        self.table.table(table)
+    }
+
+    /// Get crate Cargo.toml if loaded.
+    pub fn manifest(&self) -> Option<&TomlDocument> {
+        self.manifest.as_ref()
+    }
+
+    /// Get absolute paths of all files read to create settings.
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Returns the cfg name used in `#[cfg_attr(name, doc(cfg()))]` documentation tags or None if tags are not generated.
+    ///
+    /// `docsrs` setting has precedence over Cargo.toml detection. Without Cargo, default is no tags.
+    /// Tags are never generated when `docsrs_mode` is `auto_cfg`, letting `doc_auto_cfg` do the work.
+    pub fn docsrs_cfg(&self) -> Option<String> {
+        // 1. Mode relying on doc_auto_cfg doesn't need tags. Any other value is considered doc_cfg.
+        if let Some(DOCSRS_MODE_AUTO_CFG) = self.setting(FILE_KEY_DOCSRS_MODE, DOCSRS_MODE_KEY).as_deref() {
+            return None;
+        }
+
+        // 2. Setting from configuration
+        match self.setting(FILE_KEY_DOCSRS, DOCSRS_KEY).as_deref() {
+            Some("true") => Some(self.docsrs_cfg_name()),
+            Some("false") => None,
+
+            // 3. Detect from [package.metadata.docs.rs] in Cargo.toml
+            _ => match self.manifest() {
+                Some(manifest) => docsrs_cfg_from_manifest(manifest, self.setting(FILE_KEY_DOCSRS_CFG, DOCSRS_CFG_KEY).as_deref()),
+                None => None,   // Not built with Cargo
+            },
+        }
+    }
+
+    /// Returns the cfg name set for docs.rs tags or `docsrs` by default.
+    pub fn docsrs_cfg_name(&self) -> String {
+        self.setting(FILE_KEY_DOCSRS_CFG, DOCSRS_CFG_KEY).unwrap_or(String::from(DOCSRS_DEFAULT_CFG))
+    }
+
+    /// Keep absolute path of a file read to create settings.
+    fn track(&mut self, path : &Path) {
+        self.files.push(fs::canonicalize(path).unwrap_or(path.to_path_buf()));
+    }
+}
+
+/// Returns the cfg name passed to docs.rs by `[package.metadata.docs.rs]` table of Cargo.toml.
+///
+/// `rustdoc-args` and `cargo-args` are searched for `--cfg name`. If cfg_name is None, `docsrs` is
+/// searched first, then the first cfg found is used.
+fn docsrs_cfg_from_manifest(manifest : &TomlDocument, cfg_name : Option<&str>) -> Option<String> {
+
+    // 1. Collect all cfg passed to docs.rs
+    let mut cfgs : Vec<&str> = Vec::new();
+    for args in DOCSRS_ARGS.iter().filter_map(|key| manifest.get(&format!("{}{}{}", DOCSRS_TABLE, KEY_SEPARATOR, key))) {
+        let args = args.as_str_array();
+        for (i, arg) in args.iter().enumerate() {
+            if arg.eq(&DOCSRS_CFG_ARG) {
+                if let Some(cfg) = args.get(i + 1) {
+                    cfgs.push(cfg);
+                }
+            } else if let Some(cfg) = arg.strip_prefix(DOCSRS_CFG_ARG).and_then(|a| a.strip_prefix('=')) {
+                cfgs.push(cfg);
+            }
+        }
+    }
+
+    // 2. Find cfg name
+    match cfg_name {
+        Some(name) => cfgs.into_iter().find(|cfg| cfg.eq(&name)).map(String::from),
+        None => match cfgs.iter().find(|cfg| cfg.eq(&&DOCSRS_DEFAULT_CFG)) {
+            Some(cfg) => Some(String::from(*cfg)),
+            None => cfgs.first().map(|cfg| String::from(*cfg)),
+        },
+    }
+}
+
+/// Read and parse a toml file. Returns Ok(None) if file doesn't exists.
+///
+/// Error(s)
+/// Returns Err([ConfigError::Malformed]) if file is malformed.
+fn read_toml(path : &Path) -> Result<Option<TomlDocument>, ConfigError> {
+    match fs::read_to_string(path) {
+        Ok(content) => match TomlDocument::parse(&content) {
+            Ok(document) => Ok(Some(document)),
+            Err(line) => Err(ConfigError::Malformed(path.to_path_buf(), line)),
+        },
+        Err(_) => Ok(None),
+    }
+}
+
+/// Find and read the workspace root Cargo.toml of crate. Returns path and content of Cargo.toml.
+///
+/// Use `package.workspace` if set, else walk up from the crate folder to the first Cargo.toml with a `[workspace]` table.
+fn find_workspace_manifest(manifest_dir : &Path, manifest : Option<&TomlDocument>) -> Option<(PathBuf, TomlDocument)> {
+
+    // 1. Explicit workspace root path
+    if let Some(path) = manifest.and_then(|m| m.get(CARGO_PACKAGE_WORKSPACE_KEY)).and_then(|v| v.as_str()) {
+        let path = manifest_dir.join(path).join(CARGO_MANIFEST_NAME);
+        return read_toml(&path).ok().flatten().map(|workspace| (path, workspace));
+    }
+
+    // 2. Walk up from crate folder, including crate folder itself.
+    for dir in manifest_dir.ancestors() {
+        let workspace = if dir.eq(manifest_dir) {
+            manifest.cloned()
+        } else {
+            read_toml(&dir.join(CARGO_MANIFEST_NAME)).ok().flatten()
+        };
+
+        if let Some(workspace) = workspace {
+            if workspace.contains(CARGO_WORKSPACE_TABLE) {
+                return Some((dir.join(CARGO_MANIFEST_NAME), workspace));
+            }
+        }
+    }
+
+    None
+}
+
+/// Read features table and optional dependencies of Cargo.toml, which are implicit features.
+///
+/// Optional dependencies referenced as `dep:name` in features table don't have an implicit feature.
+fn read_features(manifest : &TomlDocument) -> Vec<String> {
+    let dependency = format!("{}{}", CARGO_DEPENDENCIES_TABLE, KEY_SEPARATOR);
+    let target_dependency = format!("{}{}", KEY_SEPARATOR, dependency);
+    let optional = format!("{}{}", KEY_SEPARATOR, CARGO_OPTIONAL_KEY);
+
+    // 1. Features table
+    let table = manifest.table(CARGO_FEATURES_TABLE);
+    let mut features : Vec<String> = table.iter().map(|(feature, _)| String::from(*feature)).collect();
+
+    // 2. Dependencies referenced with `dep:` prefix
+    let hidden : Vec<&str> = table.iter().flat_map(|(_, enables)| enables.as_str_array()).filter_map(|enable| enable.strip_prefix(CARGO_DEP_PREFIX)).collect();
+
+    // 3. Optional dependencies like `dependencies.name.optional` or `target.cfg.dependencies.name.optional`
+    features.extend(manifest.keys().filter(|key| matches!(manifest.get(key), Some(TomlValue::Boolean(true))))
+        .filter_map(|key| key.strip_suffix(optional.as_str()))
+        .filter_map(|key| key.strip_prefix(dependency.as_str()).or(key.rsplit_once(target_dependency.as_str()).map(|(_, name)| name)))
+        .filter(|name| !name.contains(KEY_SEPARATOR) && !hidden.contains(name))
+        .map(String::from));
+
+    features
+}
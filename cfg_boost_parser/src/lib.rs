@@ -0,0 +1,61 @@
+//! Parser, resolver and evaluator of [cfg_boost](https://crates.io/crates/cfg_boost) predicates.
+//!
+//! cfg_boost macros parse predicates like `desktop & !x86_64:ar` into a syntax tree and write it as `#[cfg()]`. This crate is that
+//! parser without `proc_macro`, working on strings and its own [tokens](Token). cfg_boost macros convert their tokens and call it,
+//! so that build scripts, tests and tools read predicates exactly like the macros do.
+//!
+//! ```toml
+//! [dependencies]
+//! cfg_boost_parser = "1.0.0"
+//! ```
+//! Aliases and predicates are resolved with a [Resolver]. [Settings] reads a configuration like `cfg_boost.toml`, then environment
+//! variables like macros do. Syntax trees and cfg predicates are evaluated against the cfg set of a target with [CfgSet].
+//! ```
+//! use cfg_boost_parser::{parse, Settings, CfgSet};
+//!
+//! let settings = Settings::parse("[aliases]\ngpu = \"vulkan:ft | opengl:ft\"").unwrap();
+//! let tree = parse("desktop & !x86_64:ar & gpu", &settings).unwrap();
+//! assert_eq!(tree.to_cfg_string(), "all(any(target_os = \"linux\",any(windows,target_os = \"macos\")),\
+//!     all(not(target_arch = \"x86_64\"),any(feature = \"vulkan\",feature = \"opengl\")))");
+//!
+//! // Cfg set written by `rustc --print cfg`
+//! let target = CfgSet::parse("target_arch=\"aarch64\"\ntarget_os=\"linux\"\nunix\nfeature=\"vulkan\"");
+//! assert!(target.eval_node(&tree));
+//! assert!(!target.eval_str("any(windows, target_arch = \"x86_64\")"));
+//! ```
+//! [Settings::load] reads the configuration of the crate being compiled like macros do, from `cfg_boost.toml`, `Cargo.toml` and
+//! `CFG_BOOST_CONFIG`.
+//!
+//! ## Procedural macros
+//! With the `proc-macro2` feature, the [macros] module parses `proc_macro2` tokens and generates `cfg(...)` meta and documentation
+//! tags, so that attributes of other macros accept the same predicates as `target_cfg!`.
+//! ```toml
+//! [dependencies]
+//! cfg_boost_parser = { version = "1.0.0", features = ["proc-macro2"] }
+//! ```
+//! ```ignore
+//! // #[mytrait(only = "desktop | wasm")]
+//! let settings = Settings::load().unwrap();
+//! let tree = parse(&only.value(), &settings).unwrap();
+//! let cfg = macros::cfg_meta(&tree);
+//! let doc_tag = macros::doc_tag(&tree, &settings).map(|tag| quote! { #[#tag] });
+//! quote! { #[#cfg] #doc_tag #item }
+//! ```
+
+/// Minimal TOML reader of configuration files and Cargo.toml.
+pub mod toml;
+
+mod parser; // Parser shared with cfg_boost macros
+
+mod config; // Configuration of the crate being compiled
+
+/// Parsing of proc_macro2 tokens and generation of `cfg(...)` meta for other macros.
+#[cfg(feature = "proc-macro2")]
+pub mod macros;
+
+#[cfg(all(test, feature = "proc-macro2"))]
+#[path = "../../tests/unit/macros.rs"]
+mod unit_tests; // Unit tests located in tests folder
+
+pub use parser::*;
+pub use config::{ConfigError, CARGO_MANIFEST_DIR, CFG_BOOST_CONFIG};
@@ -0,0 +1,41 @@
+// Parsing of proc_macro2 tokens into syntax trees and generation of `cfg(...)` meta for other macros.
+
+use proc_macro2::{TokenStream, TokenTree};
+
+use crate::parser::{self, Node, Resolver, Settings, SyntaxError, SyntaxTreeNode, Token};
+
+/// Convert proc_macro2 tokenstream into parser tokens.
+pub fn to_tokens(stream : TokenStream) -> Vec<Token> {
+    stream.into_iter().map(|t| match t {
+        TokenTree::Ident(ident) => Token::Ident(ident.to_string()),
+        TokenTree::Punct(punc) => Token::Punct(punc.as_char()),
+        TokenTree::Literal(literal) => Token::Literal(literal.to_string()),
+        TokenTree::Group(grp) => Token::Group(match grp.delimiter() {
+            proc_macro2::Delimiter::Parenthesis => parser::Delimiter::Parenthesis,
+            proc_macro2::Delimiter::Brace => parser::Delimiter::Brace,
+            proc_macro2::Delimiter::Bracket => parser::Delimiter::Bracket,
+            proc_macro2::Delimiter::None => parser::Delimiter::None,
+        }, to_tokens(grp.stream())),
+    }).collect()
+}
+
+/// Parse predicates tokens like `desktop & !x86_64:ar` into a syntax tree, like `target_cfg!` arms.
+///
+/// Error(s)
+/// Returns Err([SyntaxError]) if predicates are malformed or cannot be resolved.
+pub fn parse_stream(stream : TokenStream, resolver : &dyn Resolver) -> Result<Node, SyntaxError> {
+    SyntaxTreeNode::generate(&to_tokens(stream), resolver)
+}
+
+/// Generate `cfg(...)` meta of syntax tree, to be written in `#[...]`.
+pub fn cfg_meta(tree : &SyntaxTreeNode) -> TokenStream {
+    format!("cfg({})", tree.to_cfg_string()).parse::<TokenStream>().unwrap()
+}
+
+/// Generate `cfg_attr(docsrs, doc(cfg(...)))` documentation tag meta of syntax tree, to be written in `#[...]`.
+///
+/// Tags follow the same settings as `target_cfg!` : the cfg name is `docsrs` unless set with `docsrs_cfg` and there is no tag
+/// if `docsrs = false`, if `docsrs_mode = "auto_cfg"` or if `[package.metadata.docs.rs]` of Cargo.toml doesn't pass the cfg.
+pub fn doc_tag(tree : &SyntaxTreeNode, settings : &Settings) -> Option<TokenStream> {
+    settings.docsrs_cfg().map(|docsrs| format!("cfg_attr({}, doc(cfg({})))", docsrs, tree.to_cfg_string()).parse::<TokenStream>().unwrap())
+}
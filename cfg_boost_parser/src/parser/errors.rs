@@ -0,0 +1,84 @@
+// Errors of predicates parsing and resolution.
+
+use std::fmt;
+
+/// Possible parsing errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// Missing operator (happens when a leaf contains a space)
+    MissingOperator,
+
+    /// Empty node due to missing variable.
+    EmptyNode,
+
+    /// Invalid character used
+    InvalidCharacter(String),
+
+    /// Tokens cannot be read, like an unclosed parenthesis or quote.
+    InvalidTokens,
+
+    /// Alias written is not found
+    AliasNotFound(String),
+
+    /// Invalid configuration predicate
+    InvalidConfigurationPredicate(String),
+
+    /// Unknown value of a predefined predicate with suggested values.
+    UnknownPredicateValue(String, Vec<String>),
+
+    /// Feature not in Cargo.toml with suggested features.
+    UnknownFeature(Vec<String>),
+}
+
+/// Error message implementation.
+impl ParseError {
+    pub fn message(&self, tokens : &str) -> String {
+        match self {
+            ParseError::MissingOperator => format!("Operator `&` or '|' missing for `{:?}`. Target must not contain space.", tokens),
+            ParseError::EmptyNode =>  String::from("Empty node generated from attributes. Are you missing a statement between separator?"),
+            ParseError::InvalidCharacter(c) => format!("Invalid character `{}` for `{:?}`.", c, tokens),
+            ParseError::InvalidTokens => format!("Tokens `{}` cannot be read! Are parenthesis and quotes closed?", tokens),
+            ParseError::UnknownPredicateValue(cfg_prd, values) => format!("Unknown `{}` value in `{}`!{} Add it to `values.{}` in cfg_boost.toml or set `check_values = false` for newer targets.", cfg_prd, tokens,
+                did_you_mean(values), cfg_prd),
+            ParseError::UnknownFeature(features) => format!("Unknown feature in `{}`! It isn't in `[features]` or optional dependencies of Cargo.toml.{} Add features set by build systems to `values.ft` in cfg_boost.toml or set `check_features = false`.", tokens,
+                did_you_mean(features)),
+            ParseError::AliasNotFound(alias) => format!("Alias `{}` has no match! Is it added in config.toml as `target_cfg-{}`?", alias, alias),
+            ParseError::InvalidConfigurationPredicate(cfg_prd) => format!("Configuration predicate `{}` has no match! Is it added in config.toml as `target_cfg_predicate-{}`?", cfg_prd, cfg_prd),
+        }
+    }
+}
+
+/// Parsing error with the tokens it happened in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntaxError {
+    /// Error that happened
+    pub error : ParseError,
+
+    /// Tokens written like [to_source](super::to_source)
+    pub tokens : String,
+}
+
+impl SyntaxError {
+    /// Create an error that happened in tokens.
+    pub fn new(error : ParseError, tokens : &str) -> SyntaxError {
+        SyntaxError { error, tokens : String::from(tokens) }
+    }
+}
+
+impl fmt::Display for SyntaxError {
+    /// Write the error message.
+    fn fmt(&self, f : &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error.message(&self.tokens))
+    }
+}
+
+impl std::error::Error for SyntaxError {}
+
+/// Suggestion sentence of close values, empty if there is none.
+fn did_you_mean(values : &[String]) -> String {
+    match values.len() {
+        0 => String::new(),
+        1 => format!(" Did you mean `{}`?", values[0]),
+        _ => format!(" Did you mean one of `{}`?", values.join("`, `")),
+    }
+}
@@ -0,0 +1,104 @@
+// Evaluation of syntax trees and cfg predicates against the cfg set of a target.
+
+use std::{env, process::Command};
+
+use super::{tokens::{Token, Delimiter, tokenize}, tree::SyntaxTreeNode};
+
+/// Rustc executable key and default
+const RUSTC_KEY : &str = "RUSTC";
+const RUSTC_DEFAULT : &str = "rustc";
+
+/// Cfg predicates keywords, shared with cfg_boost macros and build scripts
+pub const CFG_ANY : &str = "any";
+pub const CFG_ALL : &str = "all";
+pub const CFG_NOT : &str = "not";
+pub const CFG_TRUE : &str = "true";
+pub const CFG_FALSE : &str = "false";
+
+/// Cfg set of a target, like printed by `rustc --print cfg`.
+#[derive(Debug, Clone, Default)]
+pub struct CfgSet {
+    /// Cfg options with their optional value
+    options : Vec<(String, Option<String>)>,
+}
+
+impl CfgSet {
+    /// Create cfg set from lines like `target_os="linux"` or `unix`, written by `rustc --print cfg`.
+    pub fn parse(cfg : &str) -> CfgSet {
+        CfgSet { options : cfg.lines().filter_map(Self::parse_line).collect() }
+    }
+
+    /// Get cfg set of a target triple from `rustc --print cfg --target`. Use `RUSTC` environment variable if set.
+    ///
+    /// Returns None if rustc fails, like for an unknown target.
+    pub fn from_target(target : &str) -> Option<CfgSet> {
+        Command::new(env::var(RUSTC_KEY).unwrap_or(String::from(RUSTC_DEFAULT))).args(["--print", "cfg", "--target", target]).output().ok()
+            .filter(|output| output.status.success()).and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|cfg| Self::parse(&cfg))
+    }
+
+    /// Parse a line like `target_os="linux"` or `unix`.
+    fn parse_line(line : &str) -> Option<(String, Option<String>)> {
+        let line = line.trim();
+        match line.split_once('=') {
+            Some((key, value)) => Some((String::from(key.trim()), Some(String::from(value.trim().trim_matches('"'))))),
+            None if !line.is_empty() => Some((String::from(line), None)),
+            None => None,
+        }
+    }
+
+    /// Returns true if cfg set has option `key` with value, or without value if None.
+    pub fn contains(&self, key : &str, value : Option<&str>) -> bool {
+        self.options.iter().any(|(k, v)| k.eq(key) && v.as_deref() == value)
+    }
+
+    /// Get values of option `key`, like `["unix"]` for `target_family`.
+    pub fn values(&self, key : &str) -> Vec<&str> {
+        self.options.iter().filter(|(k, _)| k.eq(key)).filter_map(|(_, v)| v.as_deref()).collect()
+    }
+
+    /// Evaluate a syntax tree against cfg set.
+    pub fn eval_node(&self, node : &SyntaxTreeNode) -> bool {
+        match node {
+            SyntaxTreeNode::NOT(child) => !self.eval_node(child),
+            SyntaxTreeNode::ANY(left, right) => self.eval_node(left) || self.eval_node(right),
+            SyntaxTreeNode::ALL(left, right) => self.eval_node(left) && self.eval_node(right),
+            SyntaxTreeNode::Alias(_, child) => self.eval_node(child),
+            SyntaxTreeNode::LEAF(_, predicate) => self.eval_str(predicate),
+        }
+    }
+
+    /// Evaluate a cfg predicate like `all(unix, not(target_os = "macos"))` against cfg set. Unreadable predicate is false.
+    pub fn eval_str(&self, predicate : &str) -> bool {
+        tokenize(predicate).map(|tokens| self.eval(&tokens)).unwrap_or(false)
+    }
+
+    /// Evaluate cfg predicate tokens against cfg set.
+    pub fn eval(&self, predicate : &[Token]) -> bool {
+
+        match predicate {
+            // any(..), all(..) and not(..)
+            [Token::Ident(ident), Token::Group(Delimiter::Parenthesis, group)] => {
+                let mut operands = group.split(|t| matches!(t, Token::Punct(','))).filter(|operand| !operand.is_empty()).map(|operand| self.eval(operand));
+                match ident.as_str() {
+                    CFG_ANY => operands.any(|value| value),
+                    CFG_ALL => operands.all(|value| value),
+                    CFG_NOT => !operands.next().unwrap_or(false),
+                    _ => false,
+                }
+            },
+            // key = "value"
+            [Token::Ident(key), Token::Punct('='), Token::Literal(value)] => self.contains(key, Some(value.trim_matches('"'))),
+            // key, true or false
+            [Token::Ident(key)] => match key.as_str() {
+                CFG_TRUE => true,
+                CFG_FALSE => false,
+                key => self.contains(key, None),
+            },
+            // Invisible group of macro_rules!
+            [Token::Group(Delimiter::None, group)] => self.eval(group),
+            _ => false,
+        }
+
+    }
+}
@@ -0,0 +1,29 @@
+// Predicates parser, resolver and evaluator working on strings and its own tokens, without proc_macro.
+//
+// Used by cfg_boost macros, which convert their TokenStream into tokens, and by build scripts, tests and tools.
+
+#[cfg(test)]
+#[path = "../../../tests/unit/parser.rs"]
+mod unit_tests; // Unit tests located in tests folder
+
+/// Parsing errors
+pub(crate) mod errors;
+
+/// Tokens and tokenizer
+pub(crate) mod tokens;
+
+/// Aliases and predicates resolution
+pub(crate) mod resolver;
+
+/// Syntax tree
+pub(crate) mod tree;
+
+/// Evaluation against a cfg set
+pub(crate) mod eval;
+
+pub use errors::{ParseError, SyntaxError};
+pub use tokens::{Token, Delimiter, tokenize, to_source};
+pub use resolver::{Resolver, Settings, resolve_alias, resolve_predicate, value_setting, ALIASES, PREDICATES, PREDICATE_NOTES, PREDICATE_VALUES, DOC_ALIAS,
+    ENV_KEY_ALIAS, ENV_KEY_PREDICATE, ENV_KEY_DESCRIPTION, ENV_KEY_VALUES, PREDICATE_PLACEHOLDER};
+pub use tree::{SyntaxTreeNode, Node, parse, json_string, NEGATIVE_SYMBOL, AND_SYMBOL, OR_SYMBOL};
+pub use eval::{CfgSet, CFG_ANY, CFG_ALL, CFG_NOT, CFG_TRUE, CFG_FALSE};
@@ -0,0 +1,309 @@
+// Resolution of aliases and predicates from built-in tables and cfg_boost configuration.
+
+use std::{env, path::PathBuf};
+
+use crate::toml::{TomlDocument, TomlValue};
+
+use super::errors::ParseError;
+
+// Contants
+pub const ENV_KEY_PREDICATE : &str = "cfg_boost_predicate-";        // Key used to fetch custom predicate
+pub const ENV_KEY_ALIAS : &str = "cfg_boost-";                      // Key used to fetch custom aliases
+pub const ENV_KEY_DESCRIPTION : &str = "cfg_boost_description-";    // Key used to fetch aliases description
+pub const ENV_KEY_VALUES : &str = "cfg_boost_values-";              // Key used to fetch additional predicate values
+pub const PREDICATE_PLACEHOLDER : &str = "{}";                      // Predicate placeholder
+const CHECK_VALUES_KEY : &str = "cfg_boost_check_values";           // Key for cfg_boost predicate values validation parameter.
+const CHECK_FEATURES_KEY : &str = "cfg_boost_check_features";       // Key for cfg_boost features validation parameter.
+const FILE_KEY_ALIAS : &str = "aliases.";                           // Configuration file key of aliases table
+const FILE_KEY_PREDICATE : &str = "predicates.";                    // Configuration file key of predicates table
+const FILE_KEY_DESCRIPTION : &str = "descriptions.";                // Configuration file key of aliases description table
+const FILE_KEY_CHECK_VALUES : &str = "check_values";                // Configuration file key for predicate values validation.
+const FILE_KEY_CHECK_FEATURES : &str = "check_features";            // Configuration file key for features validation.
+const FILE_KEY_VALUES : &str = "values.";                           // Configuration file key of additional predicate values table
+const FEATURE_PREDICATE : &str = "ft";                              // Feature predicate validated against Cargo.toml
+const PREDICATE_SEPARATOR : char = ':';                             // Separator between label and predicate
+const VALUES_SEPARATOR : char = ',';                                // Additional predicate values separator.
+const VALUES_LIST_MAX : usize = 8;                                  // Max values count listed in unknown value error when none is close.
+pub const DOC_ALIAS : &str = "doc";                                 // Doc alias
+
+// Aliases
+pub const ALIASES : [(&str, &str); 12] = [
+    ("linux", "linux:os"),                              // Linux alias and value
+    ("unix", "unix:_"),                                 // Unix alias and value
+    ("windows", "windows:_"),                           // Windows alias and value
+    ("macos", "macos:os"),                              // Macos alias and value
+    ("android", "android:os"),                          // Android alias and value
+    ("ios", "ios:os"),                                  // Ios alias and value
+    ("wasm", "wasm:_"),                                 // Wasm alias and value
+    (DOC_ALIAS, "doc:_"),                               // Doc alias and value
+    ("test", "test:_"),                                 // Test alias and value
+    ("debug", "debug_assertions:_"),                    // Debug alias and value
+    ("desktop", "linux:os | windows:_ | macos:os"),     // Desktop alias and value
+    ("mobile", "android:os | ios:os")                   // Mobile alias and value
+];
+
+// Predicates
+pub const PREDICATES : [(&str, &str); 12] = [
+    ("ar", "target_arch = \"{}\""),             // Target architecture predicate
+    ("tf", "target_feature = \"{}\""),          // Target feature predicate
+    ("os", "target_os = \"{}\""),               // Target os predicate
+    ("fm", "target_family = \"{}\""),           // Target family predicate
+    ("ev", "target_env = \"{}\""),              // Target environment predicate
+    ("ed", "target_endian = \"{}\""),           // Target endian predicate
+    ("pw", "target_pointer_width = \"{}\""),    // Target pointer width predicate
+    ("vn", "target_vendor = \"{}\""),           // Target vendor predicate
+    ("at", "target_has_atomic = \"{}\""),       // Target has atomic predicate
+    ("pn", "panic = \"{}\""),                   // Panic predicate
+    ("ft", "feature = \"{}\""),                 // Feature predicate
+    ("_", PREDICATE_PLACEHOLDER)                // Wildcard predicate
+];
+
+// Predicates notes used in availability notes. Empty note only show value.
+pub const PREDICATE_NOTES : [(&str, &str); 12] = [
+    ("ar", "architecture"),                     // Target architecture note
+    ("tf", "target feature"),                   // Target feature note
+    ("os", ""),                                 // Target os note
+    ("fm", "family"),                           // Target family note
+    ("ev", "environment"),                      // Target environment note
+    ("ed", "endian"),                           // Target endian note
+    ("pw", "bits pointer width"),               // Target pointer width note
+    ("vn", "vendor"),                           // Target vendor note
+    ("at", "bits atomic"),                      // Target has atomic note
+    ("pn", "panic"),                            // Panic note
+    ("ft", "feature"),                          // Feature note
+    ("_", "")                                   // Wildcard note
+];
+
+// Known values of predefined predicates, from rustc well-known configuration. Extended with `values` table.
+pub const PREDICATE_VALUES : [(&str, &[&str]); 9] = [
+    ("ar", &["aarch64", "amdgpu", "arm", "arm64ec", "avr", "bpf", "csky", "hexagon", "loongarch32", "loongarch64", "m68k", "mips", "mips32r6", "mips64", "mips64r6",
+        "msp430", "nvptx64", "powerpc", "powerpc64", "riscv32", "riscv64", "s390x", "sparc", "sparc64", "wasm32", "wasm64", "x86", "x86_64", "xtensa"]),
+    ("os", &["aix", "amdhsa", "android", "cuda", "cygwin", "dragonfly", "emscripten", "espidf", "freebsd", "fuchsia", "haiku", "helenos", "hermit", "horizon", "hurd",
+        "illumos", "ios", "l4re", "linux", "lynxos178", "macos", "managarm", "motor", "netbsd", "none", "nto", "nuttx", "openbsd", "psp", "psx", "qurt", "redox", "rtems",
+        "solaris", "solid_asp3", "teeos", "trusty", "tvos", "uefi", "unknown", "vexos", "visionos", "vita", "vxworks", "wasi", "watchos", "windows", "xous", "zkvm"]),
+    ("fm", &["unix", "wasm", "windows"]),
+    ("ev", &["gnu", "macabi", "mlibc", "msvc", "musl", "newlib", "nto70", "nto71", "nto71_iosock", "nto80", "ohos", "p1", "p2", "p3", "relibc", "sgx", "sim", "uclibc", "v5"]),
+    ("ed", &["big", "little"]),
+    ("pw", &["16", "32", "64"]),
+    ("vn", &["amd", "apple", "espressif", "fortanix", "ibm", "kmc", "mti", "nintendo", "nvidia", "openwrt", "pc", "risc0", "sony", "sun", "unikraft", "unknown", "uwp",
+        "vex", "win7", "wrs"]),
+    ("at", &["8", "16", "32", "64", "128", "ptr"]),
+    ("pn", &["abort", "immediate-abort", "unwind"]),
+];
+
+/// Source of cfg_boost settings used to resolve aliases and predicates.
+///
+/// [Settings] implements it with cfg_boost configuration files, tools can implement it with their own sources.
+pub trait Resolver {
+    /// Get a setting from configuration file key like `aliases.desktop`, then from environment variable key like `cfg_boost-desktop`.
+    fn setting(&self, file_key : &str, env_key : &str) -> Option<String>;
+
+    /// Get features of Cargo.toml, including optional dependencies. Features aren't validated if None.
+    fn features(&self) -> Option<&[String]> {
+        None
+    }
+}
+
+/// Settings read from a cfg_boost configuration table like `cfg_boost.toml`, then from environment variables.
+#[derive(Default)]
+pub struct Settings {
+    pub(crate) table : TomlDocument,                // Configuration table
+    pub(crate) features : Option<Vec<String>>,      // Features validated by `ft` predicate
+    pub(crate) manifest : Option<TomlDocument>,     // Crate Cargo.toml, if loaded
+    pub(crate) files : Vec<PathBuf>,                // Files read to create settings
+}
+
+impl Settings {
+    /// Create settings from content of a configuration file like `cfg_boost.toml`.
+    ///
+    /// Error(s)
+    /// Returns Err(line) if configuration is malformed.
+    pub fn parse(config : &str) -> Result<Settings, usize> {
+        Ok(Settings { table : TomlDocument::parse(config)?, ..Settings::default() })
+    }
+
+    /// Set features known by `ft` predicate, usually from Cargo.toml. Features aren't validated if not set.
+    pub fn with_features(mut self, features : Vec<String>) -> Settings {
+        self.features = Some(features);
+        self
+    }
+}
+
+impl Resolver for Settings {
+    fn setting(&self, file_key : &str, env_key : &str) -> Option<String> {
+        value_setting(self.table.get(file_key), env_key)
+    }
+
+    fn features(&self) -> Option<&[String]> {
+        self.features.as_deref()
+    }
+}
+
+/// Get a setting from configuration value, then from environment variables if value isn't set.
+pub fn value_setting(value : Option<&TomlValue>, env_key : &str) -> Option<String> {
+    match value {
+        Some(TomlValue::Other(value)) => Some(value.clone()),
+        Some(value) => match (value.as_str(), value.as_bool()) {
+            (Some(value), _) => Some(String::from(value)),
+            (_, Some(value)) => Some(value.to_string()),
+            _ => env::var(env_key).ok(),
+        },
+        None => env::var(env_key).ok(),
+    }
+}
+
+/// Parse tokens like `linux : os` to generate configuration predicate.
+///
+/// Error(s)
+/// Returns Err([ParseError::InvalidConfigurationPredicate]) if predicate not defined.
+/// Returns Err([ParseError::UnknownPredicateValue]) or Err([ParseError::UnknownFeature]) if value of a predefined predicate is unknown.
+pub fn resolve_predicate(resolver : &dyn Resolver, tokens : &str) -> Result<String, ParseError> {
+
+    // 1. Extract label and predicate from tokens
+    match tokens.find(PREDICATE_SEPARATOR) {
+        Some(position) => {
+            let label = tokens[0..position].trim();
+            let cfg_opt = tokens[position + 1..].trim();
+
+            // 2. Try to match configuration to see if predicate was defined in cfg_boost.toml, Cargo.toml or config.toml.
+            match resolver.setting(&format!("{}{}", FILE_KEY_PREDICATE, cfg_opt), &format!("{}{}", ENV_KEY_PREDICATE, cfg_opt)) {
+                Some(cfg_value) => Ok(cfg_value.replace(PREDICATE_PLACEHOLDER, label)),
+                None =>  {
+                    // 3. Find predefined predicates
+                    match PREDICATES.iter().find(|p| p.0.eq(cfg_opt)){
+                        // Predicate found, return value if known
+                        Some(pred) =>  {
+                            check_predicate_value(resolver, label, cfg_opt)?;
+                            Ok(pred.1.replace(PREDICATE_PLACEHOLDER, label))
+                        },
+
+                        // Not found, raise error.
+                        None => Err(ParseError::InvalidConfigurationPredicate(String::from(cfg_opt))),
+                    }
+                },
+            }
+        },
+
+        // Should never happen but good to have in hand
+        None => Err(ParseError::InvalidConfigurationPredicate(String::from(tokens))),
+    }
+
+}
+
+/// Verify that label is a known value of predefined predicate, a feature of Cargo.toml for `ft`, or is added in `values` table.
+///
+/// Error(s)
+/// Returns Err([ParseError::UnknownPredicateValue]) or Err([ParseError::UnknownFeature]) with close values if label is unknown.
+#[inline(always)]
+fn check_predicate_value(resolver : &dyn Resolver, label : &str, cfg_opt : &str) -> Result<(), ParseError> {
+
+    // 1. Get known values of predicate, if any. Features are only known with Cargo.toml.
+    let features : Vec<&str>;
+    let values : &[&str] = match PREDICATE_VALUES.iter().find(|v| v.0.eq(cfg_opt)) {
+        Some(values) => values.1,
+        None if cfg_opt.eq(FEATURE_PREDICATE) => match resolver.features() {
+            Some(manifest_features) => {
+                features = manifest_features.iter().map(String::as_str).collect();
+                &features
+            },
+            None => return Ok(()),
+        },
+        None => return Ok(()),
+    };
+
+    if values.contains(&label) {
+        return Ok(());
+    }
+
+    // 2. Values added in configuration and validation escape hatches.
+    let added = resolver.setting(&format!("{}{}", FILE_KEY_VALUES, cfg_opt), &format!("{}{}", ENV_KEY_VALUES, cfg_opt)).unwrap_or_default();
+    let checked = if cfg_opt.eq(FEATURE_PREDICATE) {
+        is_switch_on(resolver, FILE_KEY_CHECK_FEATURES, CHECK_FEATURES_KEY)
+    } else {
+        is_switch_on(resolver, FILE_KEY_CHECK_VALUES, CHECK_VALUES_KEY)
+    };
+    if added.split(VALUES_SEPARATOR).any(|value| value.trim().eq(label)) || !checked {
+        return Ok(());
+    }
+
+    // 3. Suggest close values or list them all if there is only a few.
+    let distance = usize::max(1, label.len() / 3);
+    let mut suggestions : Vec<String> = values.iter().filter(|value| edit_distance(label, value) <= distance).map(|value| String::from(*value)).collect();
+    if suggestions.is_empty() && values.len() <= VALUES_LIST_MAX {
+        suggestions = values.iter().map(|value| String::from(*value)).collect();
+    }
+
+    if cfg_opt.eq(FEATURE_PREDICATE) {
+        Err(ParseError::UnknownFeature(suggestions))
+    } else {
+        Err(ParseError::UnknownPredicateValue(String::from(cfg_opt), suggestions))
+    }
+
+}
+
+/// Get if a validation setting is on. If not set, default is true.
+#[inline(always)]
+fn is_switch_on(resolver : &dyn Resolver, file_key : &str, env_key : &str) -> bool {
+    match resolver.setting(file_key, env_key) {
+        Some(value) => !value.eq("false"),
+        None => true,
+    }
+}
+
+/// Levenshtein distance between 2 strings, used for suggestions.
+fn edit_distance(left : &str, right : &str) -> usize {
+
+    let right : Vec<char> = right.chars().collect();
+    let mut row : Vec<usize> = (0..=right.len()).collect();
+
+    for (i, lc) in left.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, rc) in right.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = usize::min(usize::min(row[j], above) + 1, diagonal + if lc.eq(rc) { 0 } else { 1 });
+            diagonal = above;
+        }
+    }
+
+    row[right.len()]
+
+}
+
+/// Parse label to generate alias content.
+///
+/// Error(s)
+/// Returns Err([ParseError::AliasNotFound]) if alias not defined.
+pub fn resolve_alias(resolver : &dyn Resolver, label : &str) -> Result<String, ParseError> {
+
+    // 1. Try to match configuration to see if it was defined in cfg_boost.toml, Cargo.toml or config.toml.
+    match resolver.setting(&format!("{}{}", FILE_KEY_ALIAS, label), &format!("{}{}", ENV_KEY_ALIAS, label)) {
+        Some(alias) => Ok(alias),
+        None => {
+            // 2. Find predefined alias
+            match ALIASES.iter().find(|a| a.0.eq(label)){
+                // Alias found, return value
+                Some(alias) => Ok(String::from(alias.1)),
+
+                // Not found, raise error.
+                None => Err(ParseError::AliasNotFound(String::from(label))),
+            }
+        },
+    }
+
+}
+
+/// Get alias description used in availability notes, if defined.
+#[inline(always)]
+pub(crate) fn get_alias_description(resolver : &dyn Resolver, label : &str) -> Option<String> {
+    resolver.setting(&format!("{}{}", FILE_KEY_DESCRIPTION, label), &format!("{}{}", ENV_KEY_DESCRIPTION, label))
+}
+
+/// Get predicate note used in availability notes. Custom predicates note is their label.
+#[inline(always)]
+pub(crate) fn get_predicate_note(label : &str) -> &str {
+    match PREDICATE_NOTES.iter().find(|p| p.0.eq(label)) {
+        Some(note) => note.1,
+        None => label,
+    }
+}
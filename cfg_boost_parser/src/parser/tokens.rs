@@ -0,0 +1,167 @@
+// Tokens of predicates, independent from proc_macro so predicates can be parsed from strings.
+
+use std::{fmt, iter::Peekable, str::Chars};
+
+use super::errors::ParseError;
+
+/// Delimiter of a group of tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// `( ... )`
+    Parenthesis,
+
+    /// `{ ... }`
+    Brace,
+
+    /// `[ ... ]`
+    Bracket,
+
+    /// Invisible delimiter, like groups of macro_rules! expansions.
+    None,
+}
+
+impl Delimiter {
+    /// Get opening and closing characters of delimiter, if visible.
+    fn chars(&self) -> Option<(char, char)> {
+        match self {
+            Delimiter::Parenthesis => Some(('(', ')')),
+            Delimiter::Brace => Some(('{', '}')),
+            Delimiter::Bracket => Some(('[', ']')),
+            Delimiter::None => None,
+        }
+    }
+}
+
+/// Token of predicates and cfg.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    /// Identifier like `linux`, `x86_64` or `_`.
+    Ident(String),
+
+    /// Punctuation character like `&`, `!` or `:`.
+    Punct(char),
+
+    /// Literal like `64` or `"linux"`, written with its quotes.
+    Literal(String),
+
+    /// Tokens inside a delimiter.
+    Group(Delimiter, Vec<Token>),
+}
+
+impl Token {
+    /// Returns true if token is a word (identifier or literal) that needs a space before another word.
+    fn is_word(&self) -> bool {
+        matches!(self, Token::Ident(_) | Token::Literal(_))
+    }
+
+    /// Returns true if token is a punctuation written with spaces around, like `linux : os` or `key = "value"`.
+    fn is_spaced(&self) -> bool {
+        matches!(self, Token::Punct(':' | '=' | '&' | '|'))
+    }
+}
+
+impl fmt::Display for Token {
+    /// Write the token as source.
+    fn fmt(&self, f : &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Ident(value) | Token::Literal(value) => write!(f, "{}", value),
+            Token::Punct(c) => write!(f, "{}", c),
+            Token::Group(delimiter, tokens) => match delimiter.chars() {
+                Some((open, close)) => write!(f, "{}{}{}", open, to_source(tokens), close),
+                None => write!(f, "{}", to_source(tokens)),
+            },
+        }
+    }
+}
+
+/// Write tokens as source like `linux : os & !(x86_64 : ar)`.
+///
+/// Words are separated by a space, as well as `:`, `=`, `&` and `|`. Other tokens are glued.
+pub fn to_source(tokens : &[Token]) -> String {
+
+    let mut source = String::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        if let Some(previous) = index.checked_sub(1).map(|index| &tokens[index]) {
+            if (previous.is_word() && token.is_word()) || previous.is_spaced() || token.is_spaced() || matches!(previous, Token::Punct(',')) {
+                source.push(' ');
+            }
+        }
+        source.push_str(&token.to_string());
+    }
+
+    source
+
+}
+
+/// Split source like `desktop & !(x86_64:ar)` into tokens.
+///
+/// Error(s)
+/// Returns Err([ParseError::InvalidTokens]) if a delimiter or a quote isn't closed.
+pub fn tokenize(source : &str) -> Result<Vec<Token>, ParseError> {
+
+    let mut chars = source.chars().peekable();
+
+    match tokenize_group(&mut chars, None)? {
+        (tokens, None) => Ok(tokens),
+        _ => Err(ParseError::InvalidTokens),
+    }
+
+}
+
+/// Tokenize until closing character of group is reached. Returns tokens and closing character found, if any.
+fn tokenize_group(chars : &mut Peekable<Chars>, close : Option<char>) -> Result<(Vec<Token>, Option<char>), ParseError> {
+
+    let mut tokens : Vec<Token> = Vec::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            // 1. Groups
+            '(' | '{' | '[' => {
+                let (delimiter, expected) = match c {
+                    '(' => (Delimiter::Parenthesis, ')'),
+                    '{' => (Delimiter::Brace, '}'),
+                    _ => (Delimiter::Bracket, ']'),
+                };
+                match tokenize_group(chars, Some(expected))? {
+                    (group, Some(_)) => tokens.push(Token::Group(delimiter, group)),
+                    (_, None) => return Err(ParseError::InvalidTokens),
+                }
+            },
+            ')' | '}' | ']' => return if close == Some(c) { Ok((tokens, close)) } else { Err(ParseError::InvalidTokens) },
+
+            // 2. String literal with its quotes
+            '"' => {
+                let mut literal = String::from(c);
+                loop {
+                    match chars.next() {
+                        Some('\\') => {
+                            literal.push('\\');
+                            literal.extend(chars.next());
+                        },
+                        Some('"') => break,
+                        Some(c) => literal.push(c),
+                        None => return Err(ParseError::InvalidTokens),
+                    }
+                }
+                literal.push('"');
+                tokens.push(Token::Literal(literal));
+            },
+
+            // 3. Words. Numbers are literals.
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut word = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                    word.push(c);
+                }
+                tokens.push(if c.is_ascii_digit() { Token::Literal(word) } else { Token::Ident(word) });
+            },
+
+            c if c.is_whitespace() => {},
+            c => tokens.push(Token::Punct(c)),
+        }
+    }
+
+    Ok((tokens, None))
+
+}
@@ -0,0 +1,262 @@
+// Syntax tree of cfg_boost predicates like `desktop & !x86_64:ar`.
+
+use std::rc::Rc;
+
+use super::{errors::{ParseError, SyntaxError}, tokens::{Token, tokenize, to_source}, resolver::{Resolver, resolve_alias, resolve_predicate, get_alias_description, get_predicate_note}};
+
+/// SyntaxTreeNode in a RC
+pub type Node = Rc<SyntaxTreeNode>;
+
+/// Negative symbol
+pub const NEGATIVE_SYMBOL : char = '!';
+
+/// Symbol for AND.
+pub const AND_SYMBOL : char = '&';
+
+/// Symbol for OR.
+pub const OR_SYMBOL : char = '|';
+
+/// Symbol between label and predicate of a leaf.
+const LEAF_SYMBOL : char = ':';
+
+/// Parse predicates like `desktop & !x86_64:ar` into a syntax tree, resolving aliases and predicates.
+///
+/// Error(s)
+/// Returns Err([SyntaxError]) if predicates are malformed or cannot be resolved.
+pub fn parse(source : &str, resolver : &dyn Resolver) -> Result<Node, SyntaxError> {
+    match tokenize(source) {
+        Ok(tokens) => SyntaxTreeNode::generate(&tokens, resolver),
+        Err(err) => Err(SyntaxError::new(err, source)),
+    }
+}
+
+/// Syntax tree node used to parse attribute tokens.
+#[derive(Debug, PartialEq)]
+pub enum SyntaxTreeNode {
+    /// A Not node
+    NOT(Node),
+
+    /// A or(|) operation
+    ANY(Node, Node),
+
+    /// A and(&) operation
+    ALL(Node, Node),
+
+    /// End leaf of the tree with its tokens like `linux : os` and its predicate like `target_os = "linux"`
+    LEAF(String, String),
+
+    /// Alias label with its unwrapped node
+    Alias(String, Node),
+}
+
+impl SyntaxTreeNode {
+    /// Create a NOT SyntaxTreeNode
+    pub fn not_node(child : Node) -> Node {
+        Rc::new(SyntaxTreeNode::NOT(child))
+    }
+
+    /// Create an ALL SyntaxTreeNode
+    pub fn all_node(left : Node, right : Node) -> Node {
+        Rc::new(SyntaxTreeNode::ALL(left, right))
+    }
+
+    /// Create an ANY SyntaxTreeNode
+    pub fn any_node(left : Node, right : Node) -> Node {
+        Rc::new(SyntaxTreeNode::ANY(left, right))
+    }
+
+    /// Write the node as string. The format will be the same as used with #[cfg()].
+    pub fn to_cfg_string(&self) -> String {
+        match self {
+            SyntaxTreeNode::NOT(node) => format!("not({})", node.to_cfg_string()),
+            SyntaxTreeNode::ANY(left_node, right_node) => format!("any({},{})", left_node.to_cfg_string(), right_node.to_cfg_string()),
+            SyntaxTreeNode::ALL(left_node, right_node) => format!("all({},{})", left_node.to_cfg_string(), right_node.to_cfg_string()),
+            SyntaxTreeNode::LEAF(_, predicate) => predicate.clone(),
+            SyntaxTreeNode::Alias(_, node) => node.to_cfg_string(),
+        }
+    }
+
+    /// Write the node as a human readable note like `**desktop** or **wasm**`.
+    ///
+    /// Aliases are written with their name or description instead of their content.
+    pub fn to_note(&self, resolver : &dyn Resolver) -> String {
+        match self {
+            SyntaxTreeNode::NOT(node) => match node.as_ref() {
+                SyntaxTreeNode::ANY(_, _) | SyntaxTreeNode::ALL(_, _) => format!("not ({})", node.to_note(resolver)),
+                _ => format!("non-{}", node.to_note(resolver)),
+            },
+            SyntaxTreeNode::ANY(left_node, right_node) => format!("{} or {}", left_node.to_note_operand(self, resolver), right_node.to_note_operand(self, resolver)),
+            SyntaxTreeNode::ALL(left_node, right_node) => format!("{} and {}", left_node.to_note_operand(self, resolver), right_node.to_note_operand(self, resolver)),
+            SyntaxTreeNode::LEAF(label, _) => match label.split_once(LEAF_SYMBOL) {
+                Some((label, predicate)) => match get_predicate_note(predicate.trim()) {
+                    "" => format!("**{}**", label.trim()),
+                    note => format!("**{} {}**", label.trim(), note),
+                },
+                None => format!("**{}**", label),
+            },
+            SyntaxTreeNode::Alias(label, _) => format!("**{}**", get_alias_description(resolver, label).unwrap_or(label.clone())),
+        }
+    }
+
+    /// Write node note as an operand of parent, adding parenthesis when operator differs.
+    fn to_note_operand(&self, parent : &SyntaxTreeNode, resolver : &dyn Resolver) -> String {
+        match (self, parent) {
+            (SyntaxTreeNode::ANY(_, _), SyntaxTreeNode::ALL(_, _)) | (SyntaxTreeNode::ALL(_, _), SyntaxTreeNode::ANY(_, _)) => format!("({})", self.to_note(resolver)),
+            _ => self.to_note(resolver),
+        }
+    }
+
+    /// Write the node as JSON object like `{"any":[{"leaf":"linux : os","predicate":"target_os = \"linux\""},...]}`.
+    pub fn to_json(&self) -> String {
+        match self {
+            SyntaxTreeNode::NOT(node) => format!("{{\"not\":{}}}", node.to_json()),
+            SyntaxTreeNode::ANY(left_node, right_node) => format!("{{\"any\":[{},{}]}}", left_node.to_json(), right_node.to_json()),
+            SyntaxTreeNode::ALL(left_node, right_node) => format!("{{\"all\":[{},{}]}}", left_node.to_json(), right_node.to_json()),
+            SyntaxTreeNode::LEAF(label, predicate) => format!("{{\"leaf\":{},\"predicate\":{}}}", json_string(label), json_string(predicate)),
+            SyntaxTreeNode::Alias(label, node) => format!("{{\"alias\":{},\"node\":{}}}", json_string(label), node.to_json()),
+        }
+    }
+
+    /// Generate a SyntaxTreeNode from tokens.
+    ///
+    /// Operators have no precedence : tokens are split at the first `&` or `|` and the right side is generated recursively.
+    ///
+    /// Error(s)
+    /// Returns Err([SyntaxError]) with the tokens where parsing failed.
+    pub fn generate(tokens : &[Token], resolver : &dyn Resolver) -> Result<Node, SyntaxError> {
+
+        match split_at_operator(tokens)? {
+            // Means we have to split
+            Some((operator, left, right)) =>
+                match operator {
+                    AND_SYMBOL => Ok(Self::all_node(Self::generate(left, resolver)?, Self::generate(right, resolver)?)),    // ALL node
+                    OR_SYMBOL => Ok(Self::any_node(Self::generate(left, resolver)?, Self::generate(right, resolver)?)),     // ANY node
+                    _ => Err(SyntaxError::new(ParseError::InvalidCharacter(String::from(operator)), &to_source(tokens))),
+                },
+            // No split. Must evaluate if not node, etc...
+            None => {
+                // Is NOT node?
+                let (is_not_node, content) = extract_negative_symbol(tokens)?;
+
+                if is_not_node {
+                    // Create a NOT node
+                    return Ok(Self::not_node(Self::generate(content, resolver)?));
+                }
+
+                // Extract group
+                if let Some(group) = extract_group(content) {
+                    return Self::generate(group, resolver);
+                }
+
+                // Verify that node isn't empty.
+                let source = to_source(content);
+                if source.is_empty() {
+                    return Err(SyntaxError::new(ParseError::EmptyNode, &source));
+                }
+
+                match content.iter().position(|t| matches!(t, Token::Punct(LEAF_SYMBOL))) {
+                    Some(position) => {    // End LEAF reached
+                        if to_source(&content[..position]).contains(' ') {    // Make sure node doesn't contains spaces.
+                            return Err(SyntaxError::new(ParseError::MissingOperator, &source));
+                        }
+
+                        match resolve_predicate(resolver, &source) {
+                            Ok(predicate) => Ok(Rc::new(SyntaxTreeNode::LEAF(source, predicate))),
+                            Err(err) => Err(SyntaxError::new(err, &source)),
+                        }
+                    },
+                    None => {   // Unwrap alias
+                        if source.contains(' ') {    // Make sure node doesn't contains spaces.
+                            return Err(SyntaxError::new(ParseError::MissingOperator, &source));
+                        }
+
+                        match resolve_alias(resolver, &source).and_then(|alias| tokenize(&alias)) {
+                            Ok(alias) => Ok(Rc::new(SyntaxTreeNode::Alias(source, Self::generate(&alias, resolver)?))),
+                            Err(err) => Err(SyntaxError::new(err, &to_source(tokens))),
+                        }
+                    },
+                }
+            },
+        }
+
+    }
+}
+
+/// Extract a group from tokens
+#[inline(always)]
+fn extract_group(tokens : &[Token]) -> Option<&[Token]> {
+    tokens.iter().find_map(|t| match t {
+        Token::Group(_, group) => Some(group.as_slice()),
+        _ => None,
+    })
+}
+
+/// Extract ! at the beginning of node.
+///
+/// Returns true if node starts with ! and the rest of the tokens without !.
+#[inline(always)]
+fn extract_negative_symbol(tokens : &[Token]) -> Result<(bool, &[Token]), SyntaxError> {
+
+    // Count of symbols before content
+    let mut count = 0;
+
+    for t in tokens {
+        match t {
+            Token::Punct(NEGATIVE_SYMBOL) => count += 1,
+            Token::Punct(c) => return Err(SyntaxError::new(ParseError::InvalidCharacter(String::from(*c)), &to_source(tokens))),
+            _ => break,
+        }
+    }
+
+    Ok((count > 0, &tokens[count..]))
+
+}
+
+/// Operator with tokens on its left and right.
+type Split<'a> = (char, &'a [Token], &'a [Token]);
+
+/// Split tokens at first operator `&` or `|`.
+///
+/// Returns operator with tokens on its left and right, or None if there is no operator.
+#[inline(always)]
+fn split_at_operator(tokens : &[Token]) -> Result<Option<Split<'_>>, SyntaxError> {
+
+    for (position, t) in tokens.iter().enumerate() {
+        if let Token::Punct(symbol) = t {
+            match *symbol {
+                AND_SYMBOL | OR_SYMBOL => return Ok(Some((*symbol, &tokens[..position], &tokens[position + 1..]))),
+
+                // Valid ignored characters
+                NEGATIVE_SYMBOL | '_' | '-' | ' ' | LEAF_SYMBOL | '.' => {},
+
+                _ => return Err(SyntaxError::new(ParseError::InvalidCharacter(String::from(*symbol)), &to_source(tokens))),
+            }
+        }
+    }
+
+    // No split happened
+    Ok(None)
+
+}
+
+/// Write string as JSON string with quotes and escaped characters.
+pub fn json_string(value : &str) -> String {
+
+    let mut json = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+
+}
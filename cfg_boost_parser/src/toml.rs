@@ -0,0 +1,435 @@
+// Minimal TOML reader used to read cfg_boost configuration files and Cargo.toml.
+//
+// Only what cfg_boost needs is supported : tables, dotted and quoted keys, strings, booleans,
+// arrays and inline tables. Numbers, dates and arrays of tables are read but kept raw or skipped.
+
+use std::collections::{HashMap, HashSet};
+
+#[cfg(test)]
+#[path = "../../tests/unit/toml.rs"]
+mod unit_tests; // Unit tests located in tests folder
+
+/// Key separator used for flattened keys.
+pub const KEY_SEPARATOR : char = '.';
+
+/// Possible TOML values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TomlValue {
+    /// Basic or literal string.
+    String(String),
+
+    /// Boolean value.
+    Boolean(bool),
+
+    /// Array of values.
+    Array(Vec<TomlValue>),
+
+    /// Any other value (numbers, dates, ...) kept as written.
+    Other(String),
+}
+
+impl TomlValue {
+    /// Returns value as &str if value is a string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            TomlValue::String(value) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns value as bool if value is a boolean or a "true"/"false" string.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            TomlValue::Boolean(value) => Some(*value),
+            TomlValue::String(value) => match value.as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns strings contained in an array. Non string values are ignored.
+    pub fn as_str_array(&self) -> Vec<&str> {
+        match self {
+            TomlValue::Array(values) => values.iter().filter_map(|v| v.as_str()).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// TOML document with flattened keys.
+///
+/// Every value is stored with its full dotted key. For example `b = 1` under `[a]` is stored as `a.b`.
+#[derive(Debug, Clone, Default)]
+pub struct TomlDocument {
+    entries : HashMap<String, TomlValue>,
+    tables : HashSet<String>,       // Declared table headers
+}
+
+impl TomlDocument {
+    /// Parse TOML content into a document.
+    ///
+    /// Error(s)
+    /// Returns Err(line) with the line number where parsing failed.
+    pub fn parse(content : &str) -> Result<TomlDocument, usize> {
+        let mut parser = TomlParser { chars : content.chars().collect(), position : 0, line : 1 };
+        let mut document = TomlDocument::default();
+
+        match parser.parse_document(&mut document) {
+            Some(_) => Ok(document),
+            None => Err(parser.line),
+        }
+    }
+
+    /// Get a value from its full dotted key.
+    pub fn get(&self, key : &str) -> Option<&TomlValue> {
+        self.entries.get(key)
+    }
+
+    /// Returns true if the document contains the key or a table with that name.
+    pub fn contains(&self, key : &str) -> bool {
+        let prefix = format!("{}{}", key, KEY_SEPARATOR);
+        self.tables.iter().chain(self.entries.keys()).any(|k| k.eq(key) || k.starts_with(&prefix))
+    }
+
+    /// Returns all full dotted keys of the document.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(|k| k.as_str())
+    }
+
+    /// Returns all entries of a table with keys relative to that table.
+    pub fn table(&self, table : &str) -> Vec<(&str, &TomlValue)> {
+        let prefix = format!("{}{}", table, KEY_SEPARATOR);
+        let mut entries : Vec<(&str, &TomlValue)> = self.entries.iter()
+            .filter_map(|(k, v)| k.strip_prefix(prefix.as_str()).map(|sk| (sk, v)))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        entries
+    }
+
+    /// Insert a value with its full dotted key, replacing previous value.
+    pub fn insert(&mut self, key : String, value : TomlValue) {
+        self.entries.insert(key, value);
+    }
+
+    /// Returns a new document containing only the entries of a table, with keys relative to that table.
+    pub fn sub_document(&self, table : &str) -> TomlDocument {
+        let mut document = TomlDocument::default();
+        for (key, value) in self.table(table) {
+            document.insert(String::from(key), value.clone());
+        }
+        document
+    }
+
+    /// Merge another document into this one. Values of `other` replace existing values.
+    pub fn merge(&mut self, other : &TomlDocument) {
+        for (key, value) in &other.entries {
+            self.entries.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+/// Character parser of TOML content.
+struct TomlParser {
+    chars : Vec<char>,      // Content characters
+    position : usize,       // Current position in chars
+    line : usize,           // Current line, used for errors
+}
+
+impl TomlParser {
+    /// Parse the whole document.
+    ///
+    /// Returns None if a syntax error is found.
+    fn parse_document(&mut self, document : &mut TomlDocument) -> Option<()> {
+
+        // Current table prefix. None when inside an array of tables (skipped).
+        let mut table : Option<Vec<String>> = Some(Vec::new());
+
+        loop {
+            self.skip_whitespaces_and_newlines();
+
+            match self.peek() {
+                None => return Some(()),
+                Some('[') => {
+                    self.next();
+                    if self.peek() == Some('[') {   // Array of tables are not supported and skipped
+                        self.next();
+                        self.parse_key()?;
+                        self.expect(']')?;
+                        self.expect(']')?;
+                        table = None;
+                    } else {
+                        let key = self.parse_key()?;
+                        self.expect(']')?;
+                        document.tables.insert(Self::join_key(&key, &[]));
+                        table = Some(key);
+                    }
+                },
+                Some(_) => {
+                    let key = self.parse_key()?;
+                    self.expect('=')?;
+                    let value = self.parse_value(document, &table, &key)?;
+
+                    if let (Some(table), Some(value)) = (&table, value) {
+                        document.insert(Self::join_key(table, &key), value);
+                    }
+                },
+            }
+
+            // Only comments and spaces are allowed before end of line.
+            self.skip_whitespaces();
+            match self.peek() {
+                None | Some('\n') | Some('\r') => {},
+                _ => return None,
+            }
+        }
+
+    }
+
+    /// Parse a value. Inline tables are flattened directly into document and return None.
+    fn parse_value(&mut self, document : &mut TomlDocument, table : &Option<Vec<String>>, key : &[String]) -> Option<Option<TomlValue>> {
+        self.skip_whitespaces();
+
+        match self.peek()? {
+            '"' | '\'' => Some(Some(TomlValue::String(self.parse_string()?))),
+            '[' => {
+                self.next();
+                let mut values : Vec<TomlValue> = Vec::new();
+                loop {
+                    self.skip_whitespaces_and_newlines();
+                    match self.peek()? {
+                        ']' => { self.next(); break; },
+                        ',' => { self.next(); },
+                        _ => if let Some(value) = self.parse_value(document, &None, key)? {
+                            values.push(value);
+                        },
+                    }
+                }
+                Some(Some(TomlValue::Array(values)))
+            },
+            '{' => {
+                self.next();
+                loop {
+                    self.skip_whitespaces();
+                    match self.peek()? {
+                        '}' => { self.next(); break; },
+                        ',' => { self.next(); },
+                        _ => {
+                            let mut sub_key = key.to_vec();
+                            sub_key.extend(self.parse_key()?);
+                            self.expect('=')?;
+                            let value = self.parse_value(document, table, &sub_key)?;
+
+                            if let (Some(table), Some(value)) = (table, value) {
+                                document.insert(Self::join_key(table, &sub_key), value);
+                            }
+                        },
+                    }
+                }
+                Some(None)
+            },
+            _ => {
+                let raw = self.parse_raw();
+                match raw.as_str() {
+                    "" => None,
+                    "true" => Some(Some(TomlValue::Boolean(true))),
+                    "false" => Some(Some(TomlValue::Boolean(false))),
+                    _ => Some(Some(TomlValue::Other(raw))),
+                }
+            },
+        }
+    }
+
+    /// Parse a dotted key made of bare and quoted keys.
+    fn parse_key(&mut self) -> Option<Vec<String>> {
+        let mut key : Vec<String> = Vec::new();
+
+        loop {
+            self.skip_whitespaces();
+            match self.peek()? {
+                '"' | '\'' => key.push(self.parse_string()?),
+                _ => {
+                    let mut bare = String::new();
+                    while let Some(c) = self.peek() {
+                        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                            bare.push(c);
+                            self.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    if bare.is_empty() {
+                        return None;
+                    }
+                    key.push(bare);
+                },
+            }
+
+            self.skip_whitespaces();
+            if self.peek() == Some(KEY_SEPARATOR) {
+                self.next();
+            } else {
+                return Some(key);
+            }
+        }
+    }
+
+    /// Parse a basic, literal or multi-line string.
+    fn parse_string(&mut self) -> Option<String> {
+        let quote = self.next()?;
+        let mut value = String::new();
+
+        // Multi-line strings start with 3 quotes.
+        let multiline = self.peek() == Some(quote) && self.peek_at(1) == Some(quote);
+        if multiline {
+            self.next();
+            self.next();
+            if self.peek() == Some('\n') {  // Newline immediately following opening delimiter is trimmed.
+                self.next();
+            } else if self.peek() == Some('\r') && self.peek_at(1) == Some('\n') {
+                self.next();
+                self.next();
+            }
+        } else if self.peek() == Some(quote) {   // Empty string
+            self.next();
+            return Some(value);
+        }
+
+        loop {
+            if !multiline && self.peek() == Some('\n') {    // Basic and literal strings are single line.
+                return None;
+            }
+
+            let c = self.next()?;
+
+            if c == quote {
+                if !multiline {
+                    return Some(value);
+                }
+                // Count following quotes. The last 3 close the string, up to 2 extra quotes are content.
+                let mut count = 1;
+                while self.peek() == Some(quote) {
+                    self.next();
+                    count += 1;
+                }
+                if count >= 3 {
+                    for _ in 3..count {
+                        value.push(quote);
+                    }
+                    return Some(value);
+                }
+                for _ in 0..count {
+                    value.push(quote);
+                }
+            } else if c == '\\' && quote == '"' {
+                match self.next()? {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    'b' => value.push('\u{8}'),
+                    'f' => value.push('\u{c}'),
+                    '"' => value.push('"'),
+                    '\\' => value.push('\\'),
+                    'u' => value.push(self.parse_unicode(4)?),
+                    'U' => value.push(self.parse_unicode(8)?),
+                    '\n' | '\r' | ' ' | '\t' if multiline => {   // Line ending backslash
+                        self.skip_whitespaces_and_newlines();
+                    },
+                    _ => return None,
+                }
+            } else {
+                value.push(c);
+            }
+        }
+    }
+
+    /// Parse unicode escape of `length` hexadecimal digits.
+    fn parse_unicode(&mut self, length : usize) -> Option<char> {
+        let mut code = String::new();
+        for _ in 0..length {
+            code.push(self.next()?);
+        }
+        char::from_u32(u32::from_str_radix(&code, 16).ok()?)
+    }
+
+    /// Parse a raw value (number, date, boolean) until a delimiter.
+    fn parse_raw(&mut self) -> String {
+        let mut raw = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                ',' | ']' | '}' | '#' | '\n' | '\r' => break,
+                _ => {
+                    raw.push(c);
+                    self.next();
+                },
+            }
+        }
+        String::from(raw.trim())
+    }
+
+    /// Join table and key into a flattened key.
+    fn join_key(table : &[String], key : &[String]) -> String {
+        let mut full = table.to_vec();
+        full.extend_from_slice(key);
+        full.join(&KEY_SEPARATOR.to_string())
+    }
+
+    /// Expect next non whitespace character.
+    fn expect(&mut self, expected : char) -> Option<()> {
+        self.skip_whitespaces();
+        if self.next()? == expected {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    /// Skip spaces, tabs and comments, stopping at end of line.
+    fn skip_whitespaces(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                ' ' | '\t' => { self.next(); },
+                '#' => while let Some(c) = self.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    self.next();
+                },
+                _ => break,
+            }
+        }
+    }
+
+    /// Skip spaces, tabs, comments and newlines.
+    fn skip_whitespaces_and_newlines(&mut self) {
+        loop {
+            self.skip_whitespaces();
+            match self.peek() {
+                Some('\n') | Some('\r') => { self.next(); },
+                _ => break,
+            }
+        }
+    }
+
+    /// Peek current character.
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    /// Peek character at offset of current position.
+    fn peek_at(&self, offset : usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    /// Consume current character.
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+}
@@ -1,9 +1,10 @@
+use std::rc::Rc;
+
 use proc_macro::{TokenStream, TokenTree, Delimiter};
 
-use crate::{errors::CfgBoostError, config::{DOC_ALIAS, is_cfg_boost_autodoc, if_docsrs_enabled}, syntax::{SyntaxTreeNode, AND_SYMBOL, OR_SYMBOL, NEGATIVE_SYMBOL}, CfgBoostMacroSource};
+use cfg_boost_parser::{SyntaxTreeNode, Node, AND_SYMBOL, OR_SYMBOL, NEGATIVE_SYMBOL, DOC_ALIAS};
 
-#[allow(unused_imports)]
-use crate::config::{get_release_modifier_behaviour, ReleaseModifierBehaviour};
+use crate::{errors::CfgBoostError, config::{get_config, get_release_modifier_behaviour, ReleaseModifierBehaviour, get_cfg_boost_forced_labels, get_cfg_boost_disabled_labels, is_cfg_boost_trace}, options::MacroOptions, explain::Explainer, syntax::{item_name, to_tokens}, CfgBoostMacroSource};
 
 /// Target arm separator
 pub(crate) const ARM_SEPARATOR : char = ',';
@@ -25,11 +26,23 @@ pub(crate) const MODIFIER_ACTIVATE_VALUE: &str = "all()";   // Is always true
 
 /// Deactivate arm modifier
 pub(crate) const MODIFIER_DEACTIVATE: char = '-';
-pub(crate) const MODIFIER_DEACTIVATE_VALUE: &str = "any()";   // Is always false
+
+/// Always false predicate, used by arms disabled by environment
+pub(crate) const MODIFIER_DEACTIVATE_VALUE: &str = "any()";
+
+/// Arm label symbol and separator `'label:`
+pub(crate) const LABEL_SYMBOL: char = '\'';
+pub(crate) const LABEL_SEPARATOR: char = ':';
+
+/// Profile predicate modifiers depend on. Modifiers only apply when the compiled crate has debug assertions.
+pub(crate) const MODIFIER_PROFILE: &str = "debug_assertions";
 
 /// Panic arm modifier used to see arm parameters
 pub(crate) const MODIFIER_PANIC: char = '@'; 
 
+/// Trace arm modifier used to see arm parameters without failing compilation
+pub(crate) const MODIFIER_TRACE: char = '?';
+
 
 
 /// Enumeration of possible arm types
@@ -59,45 +72,64 @@ pub(crate) enum TargetArmModifier {
 
     /// Panic (@) modifier.
     Panic,
+
+    /// Activated by label in `CFG_BOOST_FORCE` environment variable.
+    Forced,
+
+    /// Deactivated by label in `CFG_BOOST_DISABLE` environment variable.
+    Disabled,
 }
 
 /// Struct used that contains an arm type, it's attributes and content.
 #[derive(Clone)]
 pub(crate) struct TargetArm {
     pub arm_type : TargetArmType,   // Arm type
+    pub label : Option<String>,     // Arm label
+    pub label_ts : TokenStream,     // Arm label tokenstream
     pub modifier : TargetArmModifier,   // Arm modifier
+    pub trace : bool,               // Arm parameters are traced
     pub arm_ts : TokenStream,       // Left side tokenstream
     pub pred_ts : TokenStream,      // Predicates tokenstream
     pub cfg_ts : TokenStream,       // Tokenstream for #[cfg]
+    pub stub_cfg_ts : TokenStream,  // Tokenstream for #[cfg] of doc-only stub copies
     pub attr_ts : TokenStream,      // Tokenstream for #[cfg_attr]
+    pub note_ts : TokenStream,      // Tokenstream for #[doc] availability note
+    pub exclusion_ts : TokenStream, // Cumulative exclusion of previous arms for match_cfg!
+    pub tree : Option<Node>,        // Syntax tree of simplified syntax
     pub content : TokenStream,      // Right side tokenstream
+    pub options : Rc<MacroOptions>, // Options of macro invocation
 }
 
 impl ToString for TargetArm {
     /// Transform self into string.
     fn to_string(&self) -> String {
-        format!("\nArm : {}\nSyntax : {:?}\nModifier : {:?}\nPredicates : {}\n#[cfg()] : {}\n#[cfg_attr()] : {}\nContent : {}\n", 
-        self.arm_ts.to_string(), self.arm_type, self.modifier, self.pred_ts.to_string(), self.cfg_ts.to_string(), self.attr_ts.to_string(), self.content.to_string())
+        format!("\nArm : {}\nLabel : {:?}\nSyntax : {:?}\nModifier : {:?}\nPredicates : {}\n#[cfg()] : {}\n#[cfg_attr()] : {}\nContent : {}\n", 
+        self.arm_ts.to_string(), self.label, self.arm_type, self.modifier, self.pred_ts.to_string(), self.cfg_ts.to_string(), self.attr_ts.to_string(), self.content.to_string())
     }
 }
 
 impl TargetArm {
-    /// Create a new empty normal arm.
-    pub fn new() -> TargetArm {
-        TargetArm { arm_type : TargetArmType::Simplified, modifier:TargetArmModifier::None, arm_ts : TokenStream::new(), pred_ts : TokenStream::new(), cfg_ts : TokenStream::new(), attr_ts : TokenStream::new(), content : TokenStream::new() }
+    /// Create a new empty normal arm with macro options.
+    pub fn new(options : Rc<MacroOptions>) -> TargetArm {
+        TargetArm { arm_type : TargetArmType::Simplified, label : None, label_ts : TokenStream::new(), modifier:TargetArmModifier::None, trace : false, arm_ts : TokenStream::new(), pred_ts : TokenStream::new(), cfg_ts : TokenStream::new(), stub_cfg_ts : TokenStream::new(), attr_ts : TokenStream::new(), note_ts : TokenStream::new(), exclusion_ts : TokenStream::new(), tree : None, content : TokenStream::new(), options }
     }
 
     /// Extract target arms into a vector from macro source.
     /// 
+    /// Options header `#![]` is parsed before the first arm, with options accepted by macro_name.
+    /// 
     /// Panic
     /// Will panic if no Wildcard arm inserted.
-    pub fn extract(source : TokenStream, macro_src : CfgBoostMacroSource) -> Vec<TargetArm> {
+    pub fn extract(source : TokenStream, macro_src : CfgBoostMacroSource, macro_name : &str) -> Vec<TargetArm> {
 
         // Vector of all arms
         let mut arms : Vec<TargetArm> = Vec::new();
 
+        // Options header and arms source
+        let (options, source) = MacroOptions::extract(source, macro_name);
+
         // Arm used to extract attr and content.
-        let mut arm = TargetArm::new();
+        let mut arm = TargetArm::new(Rc::new(options));
 
         // Tell if we are extracting for left_side or not
         let mut left_side = true;
@@ -123,20 +155,124 @@ impl TargetArm {
             Self::add_arm(&mut arms, &mut arm, &mut left_side);
         }
 
-        // 3. Verify arms integrity.
+        // 3. Force or disable labeled arms from environment.
+        Self::apply_labels(&mut arms);
+
+        // 4. Verify arms integrity.
         Self::verify_arms_integrity(macro_src, &mut arms);
 
-        // 4. Generate arms predicates
+        // 5. Generate arms predicates
         Self::generate_arms_predicate(macro_src, &mut arms);
 
-        // 5. Panic! for arms with @
+        // 6. Trace arms with ? or all arms if CFG_BOOST_TRACE is set
+        Self::trace_arms(&arms);
+
+        // 7. Panic! for arms with @
         Self::panic_arms(&arms);
 
-        // 6. Return arms vector
+        // 8. Return arms vector
         arms
 
     }
 
+    /// Get arm used to generate an item of arms vector, documenting items declared with the same name by many arms only once.
+    /// 
+    /// Under autodoc, the first arm declaring the item is documented with tag and note of all those arms predicates
+    /// while others are excluded from `doc`, preventing duplicate definitions.
+    pub fn item_arm(arms : &[TargetArm], arms_items : &[Vec<TokenStream>], index : usize, item : &TokenStream) -> TargetArm {
+
+        let arm = &arms[index];
+
+        // 1. Get autodoc arms declaring an item with the same name
+        let sharing : Vec<&TargetArm> = match item_name(item) {
+            Some(name) if arm.is_autodoc() => arms.iter().zip(arms_items).filter(|(other, items)| 
+                other.is_autodoc() && items.iter().any(|other_item| item_name(other_item).as_ref() == Some(&name))).map(|(other, _)| other).collect(),
+            _ => Vec::new(),
+        };
+
+        // 2. Generate arm according to its position
+        if sharing.len() <= 1 {
+            arm.clone()
+        } else if std::ptr::eq(sharing[0], arm) {
+            arm.documented_with(&sharing)
+        } else {
+            arm.undocumented()
+        }
+
+    }
+
+    /// Get copy of arm documenting an item shared by arms.
+    /// 
+    /// Tag and note are generated from the union of arms predicates.
+    fn documented_with(&self, arms : &[&TargetArm]) -> TargetArm {
+
+        let mut arm = self.clone();
+
+        // 1. Union of arms predicates
+        let pred_ts = format!("any({})", arms.iter().map(|arm| arm.pred_ts.to_string()).collect::<Vec<String>>().join(", ")).parse::<TokenStream>().unwrap();
+        let tree = arms.iter().map(|arm| arm.tree.clone()).reduce(|left, right| match (left, right) {
+            (Some(left), Some(right)) => Some(SyntaxTreeNode::any_node(left, right)),
+            _ => None,     // Legacy arms have no tree
+        }).flatten();
+
+        // 2. Stub copy is only needed if no arm is active
+        if !arm.stub_cfg_ts.is_empty() {
+            arm.stub_cfg_ts = format!("#[cfg(all({}, not(any({}))))]", DOC_ALIAS, 
+                arms.iter().map(|arm| Self::apply_modifier(arm.pred_ts.clone(), arm.modifier)).collect::<Vec<String>>().join(", ")).parse::<TokenStream>().unwrap();
+        }
+
+        // 3. Tag and note from union
+        arm.attr_ts = Self::generate_target_attr_ts(pred_ts, &self.options);
+        arm.note_ts = Self::generate_target_note_ts(tree, &self.options);
+
+        arm
+
+    }
+
+    /// Get copy of arm excluded from documentation for an item shared by arms.
+    fn undocumented(&self) -> TargetArm {
+
+        let mut arm = self.clone();
+
+        // 1. Without doc-stub mode, cfg_ts include doc and must exclude it.
+        if arm.stub_cfg_ts.is_empty() {
+            arm.cfg_ts = format!("#[cfg(all(not({}), {}))]", DOC_ALIAS, Self::apply_modifier(arm.pred_ts.clone(), arm.modifier)).parse::<TokenStream>().unwrap();
+        }
+
+        // 2. Remove documentation attributes and stub copy
+        arm.stub_cfg_ts = TokenStream::new();
+        arm.attr_ts = TokenStream::new();
+        arm.note_ts = TokenStream::new();
+
+        arm
+
+    }
+
+    /// Print arms with ? to stderr without failing compilation.
+    /// 
+    /// Every arm is printed if `CFG_BOOST_TRACE` environment variable is set.
+    #[inline(always)] 
+    fn trace_arms(arms : &[TargetArm]) {
+
+        let trace_all = is_cfg_boost_trace();
+
+        // Create arms message, explained for `CFG_BOOST_EXPLAIN` target if set.
+        let mut message = String::new();
+        let mut explainer : Option<Option<Explainer>> = None;
+        for arm in arms.iter().filter(|arm| arm.trace || trace_all) {
+            message.push_str(&arm.to_string());
+            if let Some(explainer) = explainer.get_or_insert_with(Explainer::from_env) {
+                message.push_str(&explainer.explain(arm));
+            }
+        }
+
+        // Print if message length > 0
+        if !message.is_empty() {
+            eprintln!("\n*** Macro traced by `{}` modifier or `CFG_BOOST_TRACE` ***\n{}", MODIFIER_TRACE, message);
+        }
+
+    }
+
     /// Panic for arms with @.
     #[inline(always)] 
     fn panic_arms(arms : &Vec<TargetArm>) {
@@ -160,9 +296,71 @@ impl TargetArm {
 
     }
 
+    /// Set modifier of labeled arms from `CFG_BOOST_FORCE` and `CFG_BOOST_DISABLE` environment variables.
+    /// 
+    /// Panic(s)
+    /// Will panic! if a label is both forced and disabled.
+    #[inline(always)]
+    fn apply_labels(arms : &mut [TargetArm]) {
+
+        let forced = get_cfg_boost_forced_labels();
+        let disabled = get_cfg_boost_disabled_labels();
+
+        for arm in arms.iter_mut() {
+            if let Some(label) = &arm.label {
+                match (forced.contains(label), disabled.contains(label)) {
+                    (true, true) => panic!("{}", CfgBoostError::LabelForcedAndDisabled(label.clone()).message("")),
+                    (true, false) => arm.modifier = TargetArmModifier::Forced,
+                    (false, true) => arm.modifier = TargetArmModifier::Disabled,
+                    _ => {},
+                }
+            }
+        }
+
+    }
+
+    /// Extract arm label `'label:` written before predicates.
+    /// 
+    /// Returns true if token is part of label.
+    /// 
+    /// Panic(s)
+    /// Will panic! if label syntax is incorrect.
+    #[inline(always)]
+    fn extract_label(arm : &mut TargetArm, token : TokenTree) -> bool {
+
+        // 1. Label is before predicates and start with `'`
+        let is_label = arm.arm_ts.is_empty() && arm.label.is_none() && match &token {
+            TokenTree::Punct(punct) if arm.label_ts.is_empty() => punct.as_char() == LABEL_SYMBOL,
+            _ => !arm.label_ts.is_empty(),
+        };
+
+        if !is_label {
+            return false;
+        }
+
+        // 2. Label is complete with `'`, name and `:`
+        arm.label_ts.extend(TokenStream::from(token));
+        let tokens : Vec<TokenTree> = arm.label_ts.clone().into_iter().collect();
+        if tokens.len() == 3 {
+            match (&tokens[1], &tokens[2]) {
+                (TokenTree::Ident(name), TokenTree::Punct(punct)) if punct.as_char() == LABEL_SEPARATOR => arm.label = Some(name.to_string()),
+                _ => panic!("{}", CfgBoostError::LabelSyntaxError.message(arm.label_ts.to_string().as_str())),
+            }
+        }
+
+        true
+
+    }
+
     /// Extract tokens for attributes.
     #[inline(always)]
     fn extract_attributes(arm : &mut TargetArm, token : TokenTree) {
+
+        // Arm label is extracted before predicates
+        if Self::extract_label(arm, token.clone()) {
+            return;
+        }
+
         match token.clone() {
             TokenTree::Group(grp) => {
                 match arm.arm_type {    // Make sure legacy syntax is correct
@@ -193,44 +391,23 @@ impl TargetArm {
                     }
                     arm.modifier = TargetArmModifier::Panic;
                 },
-                MODIFIER_ACTIVATE => {
+                MODIFIER_TRACE => {
                     if !arm.arm_ts.is_empty() {
                         panic!("{}", CfgBoostError::ModifierNotFirst.message(""));  // Modifier is not first character
                     }
-                    // Debug behaviour. Activate arm.
-                    #[cfg(debug_assertions)]
-                    {
-                        arm.modifier = TargetArmModifier::Activate;
-                    }
-                    // Release behaviour. Panic or Ignore.
-                    #[cfg(not(debug_assertions))]
-                    {
-                        match get_release_modifier_behaviour() {
-                            ReleaseModifierBehaviour::Panic => panic!("{}", CfgBoostError::ModifierPanicRelease.message("")),  // Modifier release panic
-                            _ => {},    // Just ignore it
-                        }
-
+                    arm.trace = true;   // Trace can be combined with other modifiers
+                },
+                MODIFIER_ACTIVATE => {
+                    if !arm.arm_ts.is_empty() {
+                        panic!("{}", CfgBoostError::ModifierNotFirst.message(""));  // Modifier is not first character
                     }
+                    arm.modifier = TargetArmModifier::Activate;
                 },
                 MODIFIER_DEACTIVATE => {
                     if !arm.arm_ts.is_empty() {
                         panic!("{}", CfgBoostError::ModifierNotFirst.message(""));  // Modifier is not first character
                     }
-                    // Debug behaviour. Activate arm.
-                    #[cfg(debug_assertions)]
-                    {
-                        arm.modifier = TargetArmModifier::Deactivate;
-                    }
-                    // Release behaviour. Panic or Ignore.
-                    #[cfg(not(debug_assertions))]
-                    {
-                        match get_release_modifier_behaviour() {
-                            ReleaseModifierBehaviour::Panic => panic!("{}", CfgBoostError::ModifierPanicRelease.message("")),  // Modifier release panic
-                            _ => {},    // Just ignore it
-                        }
-
-                    }
-
+                    arm.modifier = TargetArmModifier::Deactivate;
                 },
                 LEGACY_ARM => {
                     if !arm.arm_ts.is_empty() {
@@ -349,7 +526,7 @@ impl TargetArm {
         arms.push(arm.clone());
 
         // 2. Reset arm and separator
-        *arm = TargetArm::new();
+        *arm = TargetArm::new(arm.options.clone());
         *left_side = true;
 
     }
@@ -363,31 +540,36 @@ impl TargetArm {
             CfgBoostMacroSource::TargetMacro => {
                 // For each arm
                 arms.iter_mut().for_each(|arm| {
-                    // 1. Generate predicate_ts
-                    arm.pred_ts.extend(Self::generate_pred_ts(arm.arm_type, arm.arm_ts.clone()));
+                    // 1. Generate syntax tree and predicate_ts
+                    arm.tree = Self::generate_tree(arm.arm_type, arm.arm_ts.clone());
+                    arm.pred_ts.extend(Self::generate_pred_ts(arm.arm_type, arm.arm_ts.clone(), arm.tree.clone()));
 
                     // 2. Generate cfg_ts with doc according to modifier
-                    arm.cfg_ts.extend(Self::generate_target_cfg_ts(Self::set_default_doc(arm.pred_ts.clone()), arm.modifier));
+                    arm.cfg_ts.extend(Self::generate_target_cfg_ts(Self::set_default_doc(arm.pred_ts.clone(), &arm.options), arm.modifier));
+
+                    // 3. Generate stub_cfg_ts for doc-stub mode
+                    arm.stub_cfg_ts.extend(Self::generate_stub_cfg_ts(arm.pred_ts.clone(), arm.modifier, &arm.options));
 
-                    // 3. Generate attr_ts
-                    arm.attr_ts.extend(Self::generate_target_attr_ts(arm.pred_ts.clone()));
+                    // 4. Generate attr_ts
+                    arm.attr_ts.extend(Self::generate_target_attr_ts(arm.pred_ts.clone(), &arm.options));
+
+                    // 5. Generate note_ts
+                    arm.note_ts.extend(Self::generate_target_note_ts(arm.tree.clone(), &arm.options));
                 });
 
             },
             CfgBoostMacroSource::MatchMacro => {
-                // Debug behaviour. Set modifiers.
-                #[cfg(debug_assertions)]
-                {
-                    // If contains activated arm, deactivate all others
-                    if arms.iter().filter(|arm| match arm.modifier{
-                        TargetArmModifier::Activate => true,
-                        _ => false,
-                    }).count() > 0 {
-                        arms.iter_mut().for_each(|arm| match arm.modifier {
-                            TargetArmModifier::Activate => {},
-                            _ => arm.modifier = TargetArmModifier::Deactivate ,
-                        });
-                    }
+                // If contains activated arm, deactivate all others the same way
+                let deactivate = arms.iter().find_map(|arm| match arm.modifier{
+                    TargetArmModifier::Activate => Some(TargetArmModifier::Deactivate),
+                    TargetArmModifier::Forced => Some(TargetArmModifier::Disabled),
+                    _ => None,
+                });
+                if let Some(deactivate) = deactivate {
+                    arms.iter_mut().for_each(|arm| match arm.modifier {
+                        TargetArmModifier::Activate | TargetArmModifier::Forced => {},
+                        _ => arm.modifier = deactivate,
+                    });
                 }
 
                 // Used to accumulate tokenstream for condition. Default is all() == true
@@ -395,30 +577,19 @@ impl TargetArm {
 
                 // For each arm
                 arms.iter_mut().for_each(|arm| {
-                    // 1. Generate predicate_ts
-                    arm.pred_ts.extend(Self::generate_pred_ts(arm.arm_type, arm.arm_ts.clone()));
+                    // 1. Generate syntax tree and predicate_ts
+                    arm.tree = Self::generate_tree(arm.arm_type, arm.arm_ts.clone());
+                    arm.pred_ts.extend(Self::generate_pred_ts(arm.arm_type, arm.arm_ts.clone(), arm.tree.clone()));
 
                     // 2. Generate pred_ts from cumulatives tokenstream according to arm type
+                    arm.exclusion_ts = format!("all({})", cumul_ts).parse::<TokenStream>().unwrap();
                     let pred_ts = format!("all({},{})", cumul_ts, arm.pred_ts.clone()).parse::<TokenStream>().unwrap();
 
                     // 3. Generate cfg_ts according to modifier and pred_ts
                     arm.cfg_ts.extend(Self::generate_target_cfg_ts(pred_ts.clone(), arm.modifier));
 
                     // 4. Cumulate tokenstream for arm exclusivity.
-                    cumul_ts.extend(format!(", not({})", { // Wish I could use match_cfg! here =(
-                        #[cfg(debug_assertions)]
-                        {
-                            match arm.modifier {
-                                TargetArmModifier::Activate => MODIFIER_ACTIVATE_VALUE.parse::<TokenStream>().unwrap(),
-                                TargetArmModifier::Deactivate => MODIFIER_DEACTIVATE_VALUE.parse::<TokenStream>().unwrap(),
-                                _ => arm.pred_ts.clone(),
-                            }
-                        }
-                        #[cfg(not(debug_assertions))]
-                        {
-                            arm.pred_ts.clone()
-                        }
-                    }).parse::<TokenStream>().unwrap());
+                    cumul_ts.extend(format!(", not({})", Self::apply_modifier(arm.pred_ts.clone(), arm.modifier)).parse::<TokenStream>().unwrap());
                 });
             },
         }
@@ -428,37 +599,104 @@ impl TargetArm {
     /// Return ts created.
     #[inline(always)]
     fn generate_target_cfg_ts(pred_ts : TokenStream, modifier : TargetArmModifier) -> TokenStream {
+        format!("#[cfg({})]", Self::apply_modifier(pred_ts, modifier)).parse::<TokenStream>().unwrap()
+    }
+
+    /// Generate #[cfg] tokenstream of doc-only stub copies for target_cfg!.
+    /// 
+    /// Stub copies are only compiled for `doc` when the arm predicates don't match.
+    /// Return ts created, empty if not in doc-stub mode.
+    #[inline(always)]
+    fn generate_stub_cfg_ts(pred_ts : TokenStream, modifier : TargetArmModifier, options : &MacroOptions) -> TokenStream {
+        if Self::is_doc_stub(pred_ts.clone(), options) {
+            format!("#[cfg(all({}, not({})))]", DOC_ALIAS, Self::apply_modifier(pred_ts, modifier)).parse::<TokenStream>().unwrap()
+        } else {
+            TokenStream::new()
+        }
+    }
 
-        let mut pred_str = pred_ts.to_string();
+    /// Apply modifier to predicates.
+    /// 
+    /// Modifiers are conditional to `debug_assertions` so they follow the profile of the crate being compiled.
+    /// Arms forced or disabled by environment are not.
+    /// Return predicates string.
+    #[inline(always)]
+    pub fn apply_modifier(pred_ts : TokenStream, modifier : TargetArmModifier) -> String {
+        match modifier{
+            TargetArmModifier::Activate => format!("any({}, {})", MODIFIER_PROFILE, pred_ts),
+            TargetArmModifier::Deactivate => format!("all(not({}), {})", MODIFIER_PROFILE, pred_ts),
+            TargetArmModifier::Forced => String::from(MODIFIER_ACTIVATE_VALUE),
+            TargetArmModifier::Disabled => String::from(MODIFIER_DEACTIVATE_VALUE),
+            _ => pred_ts.to_string(),
+        }
+    }
 
-        // Debug behaviour. Generate cfg_ts according to modifier
-        #[cfg(debug_assertions)]
-        {
-            match modifier{
-                TargetArmModifier::Activate => pred_str = String::from(MODIFIER_ACTIVATE_VALUE),
-                TargetArmModifier::Deactivate => pred_str = String::from(MODIFIER_DEACTIVATE_VALUE),
-                _ => {}
-            }
+    /// Generate tokenstream applying release modifier behaviour when arms have `+` or `-` modifiers.
+    /// 
+    /// Warning needs a `const _` item thus is only generated if `item_level` is true (not in impl or trait).
+    /// Inside an impl or trait, use [TargetArm::generate_release_warning_ts] in bodies of arms items instead.
+    /// Return ts created.
+    pub fn generate_release_modifier_ts(arms : &[TargetArm], item_level : bool) -> TokenStream {
+
+        // 1. Only if an arm has a modifier.
+        if !Self::has_modifier(arms) {
+            return TokenStream::new();
+        }
+
+        // 2. Generate tokenstream for release according to behaviour
+        match get_release_modifier_behaviour() {
+            ReleaseModifierBehaviour::Panic => format!("#[cfg(not({}))] compile_error!({});", MODIFIER_PROFILE, 
+                proc_macro::Literal::string(&CfgBoostError::ModifierPanicRelease.message(""))).parse::<TokenStream>().unwrap(),
+            ReleaseModifierBehaviour::Warn if item_level => Self::generate_release_warning_ts(arms),
+            _ => TokenStream::new(),
+        }
+
+    }
+
+    /// Generate `const _` item warning that `+` or `-` modifiers are ignored on release, if release modifier behaviour is warn.
+    /// 
+    /// Return ts created, empty if arms have no modifier or behaviour isn't warn.
+    pub fn generate_release_warning_ts(arms : &[TargetArm]) -> TokenStream {
+        match (Self::has_modifier(arms), get_release_modifier_behaviour()) {
+            (true, ReleaseModifierBehaviour::Warn) => format!("#[cfg(not({}))] const _ : () = {{ #[deprecated(note = {})] const CFG_BOOST_MODIFIER : () = (); CFG_BOOST_MODIFIER }};", 
+                MODIFIER_PROFILE, proc_macro::Literal::string(&CfgBoostError::ModifierWarnRelease.message(""))).parse::<TokenStream>().unwrap(),
+            _ => TokenStream::new(),
         }
+    }
 
-        format!("#[cfg({})]", pred_str).parse::<TokenStream>().unwrap()
+    /// Returns true if an arm has a `+` or `-` modifier.
+    #[inline(always)]
+    fn has_modifier(arms : &[TargetArm]) -> bool {
+        arms.iter().any(|arm| matches!(arm.modifier, TargetArmModifier::Activate | TargetArmModifier::Deactivate))
     }
 
+    /// Generate syntax tree for simplified syntax arm.
+    /// Return tree created or None for other arm types.
+    /// 
+    /// Panic(s)
+    /// Will panic! if predicates are malformed or cannot be resolved.
+    #[inline(always)]
+    pub fn generate_tree(arm_type : TargetArmType, arm_ts : TokenStream) -> Option<Node> {
+        match arm_type {
+            TargetArmType::Simplified => match SyntaxTreeNode::generate(&to_tokens(arm_ts), get_config().as_ref()) {
+                Ok(tree) => Some(tree),
+                Err(err) => panic!("{}", err),
+            },
+            _ => None,
+        }
+    }
 
     /// Generate predicate tokenstream for arm.
     /// Return ts created.
     #[inline(always)]
-    fn generate_pred_ts(arm_type : TargetArmType, arm_ts : TokenStream) -> TokenStream {
+    fn generate_pred_ts(arm_type : TargetArmType, arm_ts : TokenStream, tree : Option<Node>) -> TokenStream {
 
-        match arm_type{
-            TargetArmType::Simplified => {
-                let syntax_tree = SyntaxTreeNode::generate(arm_ts); // Simplified predicates comes from syntax tree
-                syntax_tree.to_string().parse::<TokenStream>().unwrap()
-            },
-            TargetArmType::Legacy => {
+        match (arm_type, tree) {
+            (_, Some(syntax_tree)) => syntax_tree.to_cfg_string().parse::<TokenStream>().unwrap(), // Simplified predicates comes from syntax tree
+            (TargetArmType::Legacy, None) => {
                 Self::extract_legacy_predicates(arm_ts)
             },
-            TargetArmType::Wildcard => MODIFIER_ACTIVATE_VALUE.parse::<TokenStream>().unwrap(),  // Wildcard pred_ts is MODIFIER_ACTIVATE_VALUE
+            _ => MODIFIER_ACTIVATE_VALUE.parse::<TokenStream>().unwrap(),  // Wildcard pred_ts is MODIFIER_ACTIVATE_VALUE
         }
 
     }
@@ -466,12 +704,26 @@ impl TargetArm {
     /// Generate #[cfg_attr] tokenstream for target_cfg!.
     /// Return ts created.
     #[inline(always)]
-    fn generate_target_attr_ts(pred_ts : TokenStream) -> TokenStream {
+    fn generate_target_attr_ts(pred_ts : TokenStream, options : &MacroOptions) -> TokenStream {
 
-        if if_docsrs_enabled() {    // Only is docsrs is enabled
-            format!("#[cfg_attr(docsrs, doc(cfg({})))]", pred_ts.to_string()).parse::<TokenStream>().unwrap()
-        } else {
-            TokenStream::new()
+        match &options.docsrs_cfg {    // Only is docsrs is enabled
+            Some(docsrs) => format!("#[cfg_attr({}, doc(cfg({})))]", docsrs, pred_ts).parse::<TokenStream>().unwrap(),
+            None => TokenStream::new(),
+        }
+
+    }
+
+    /// Generate #[doc] availability note tokenstream for target_cfg!.
+    /// 
+    /// Note is written from the syntax tree so it is readable on stable `cargo doc`. Legacy arms have no note.
+    /// Return ts created.
+    #[inline(always)]
+    fn generate_target_note_ts(tree : Option<Node>, options : &MacroOptions) -> TokenStream {
+
+        match (tree, options.availability_notes) {
+            (Some(tree), true) => format!("#[doc = \"\"] #[doc = {}]", 
+                proc_macro::Literal::string(&format!("Available on {} only.", tree.to_note(get_config().as_ref())))).parse::<TokenStream>().unwrap(),
+            _ => TokenStream::new(),
         }
 
     }
@@ -479,11 +731,11 @@ impl TargetArm {
     /// Add default doc tokenstream to attributes if not present for legacy syntax.
     /// Return ts created.
     #[inline(always)]
-    fn set_default_doc(pred_ts : TokenStream) -> TokenStream {
+    fn set_default_doc(pred_ts : TokenStream, options : &MacroOptions) -> TokenStream {
 
-        // Only if env setting is true
-        if is_cfg_boost_autodoc() {
-            if Self::is_set_attr_autodoc(pred_ts.clone()) { // If already set, change nothing
+        // Only if setting is true
+        if options.autodoc {
+            if Self::is_set_attr_autodoc(pred_ts.clone()) || options.doc_stub { // If already set or documented by stub copies, change nothing
                 pred_ts
             } else {
                 format!("any({}, {})", DOC_ALIAS, pred_ts).parse::<TokenStream>().unwrap()
@@ -494,6 +746,20 @@ impl TargetArm {
 
     }
 
+    /// Return true if autodoc add `doc` to arm predicates, with or without doc-stub copies.
+    #[inline(always)]
+    fn is_autodoc(&self) -> bool {
+        self.options.autodoc && !Self::is_set_attr_autodoc(self.pred_ts.clone())
+    }
+
+    /// Return true if autodoc use doc-stub copies for predicates.
+    /// 
+    /// Predicates that already set `doc` are left as is.
+    #[inline(always)]
+    fn is_doc_stub(pred_ts : TokenStream, options : &MacroOptions) -> bool {
+        options.autodoc && options.doc_stub && !Self::is_set_attr_autodoc(pred_ts)
+    }
+
     /// Return true if item has doc set.
     #[inline(always)]
     fn is_set_attr_autodoc(attr : TokenStream) -> bool {
@@ -550,8 +816,8 @@ impl TargetArm {
                 let mut activated:usize  = 0;
 
                 arms.iter().for_each(|arm| match arm.modifier {
-                    TargetArmModifier::Activate => activated += 1,  // Increment activated arms
-                    TargetArmModifier::Deactivate => match arm.arm_type{
+                    TargetArmModifier::Activate | TargetArmModifier::Forced => activated += 1,  // Increment activated arms
+                    TargetArmModifier::Deactivate | TargetArmModifier::Disabled => match arm.arm_type{
                         TargetArmType::Wildcard => panic!("{}", CfgBoostError::MatchDeactivatedWildArm.message("")),    // Wildcard arm cannot be deativated
                         _ => {},
                     },
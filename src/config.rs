@@ -1,4 +1,8 @@
-use std::{env, path::Path, fs};
+use std::{env, path::{Path, PathBuf}, cell::RefCell, rc::Rc};
+
+use proc_macro::{TokenStream, Literal};
+
+use cfg_boost_parser::{Resolver, Settings, ConfigError, ENV_KEY_ALIAS, CARGO_MANIFEST_DIR, CFG_BOOST_CONFIG};
 
 use crate::errors::CfgBoostError;
 
@@ -7,48 +11,81 @@ use crate::errors::CfgBoostError;
 mod unit_tests; // Unit tests located in tests folder
 
 // Contants
-pub(crate) const ENV_KEY_PREDICATE : &str = "cfg_boost_predicate-";   // Key used to fetch custom predicate
-pub(crate) const ENV_KEY_ALIAS : &str = "cfg_boost-";                 // Key used to fetch custom aliases
-pub(crate) const PREDICATE_PLACEHOLDER : &str = "{}";               // Predicate placeholder
 const AUTO_DOC_KEY : &str = "cfg_boost_autodoc";                    // Key for cfg_boost autodocumentation parameter.
+const AVAILABILITY_NOTES_KEY : &str = "cfg_boost_availability_notes";  // Key for cfg_boost availability notes parameter.
+const DOC_STUB_KEY : &str = "cfg_boost_doc_stub";                   // Key for cfg_boost doc-stub mode parameter.
 const MODIFIER_BEHAVIOUR_KEY : &str = "cfg_boost_release_modifier_behaviour";                    // Key for cfg_boost release modifier behaviour parameter.
-const CFG_BOOST_CARGO_CACHE : &str = "CFG_BOOST_ATTR_DOC_SET";      // Key value of cargo.toml caching.
-const CFG_BOOST_DOCRS_TAG : &str = "[package.metadata.docs.rs]";    // Tag to search in Cargo.toml
-const CARGO_MANIFEST_DIR : &str = "CARGO_MANIFEST_DIR";             // Cargo manifest dir key
-const CARGO_MANIFEST_NAME : &str = "Cargo.toml";                    // Cargo manifest file name
-pub(crate) const DOC_ALIAS : &str = "doc";                          // Doc alias
-
-// Aliases
-pub(crate) const ALIASES : [(&str, &str); 12] = [
-    ("linux", "linux:os"),                              // Linux alias and value
-    ("unix", "unix:_"),                                 // Unix alias and value
-    ("windows", "windows:_"),                           // Windows alias and value
-    ("macos", "macos:os"),                              // Macos alias and value
-    ("android", "android:os"),                          // Android alias and value
-    ("ios", "ios:os"),                                  // Ios alias and value
-    ("wasm", "wasm:_"),                                 // Wasm alias and value
-    (DOC_ALIAS, "doc:_"),                               // Doc alias and value
-    ("test", "test:_"),                                 // Test alias and value
-    ("debug", "debug_assertions:_"),                    // Debug alias and value
-    ("desktop", "linux:os | windows:_ | macos:os"),     // Desktop alias and value
-    ("mobile", "android:os | ios:os")                   // Mobile alias and value
-];
-
-// Predicates
-pub(crate) const PREDICATES : [(&str, &str); 12] = [
-    ("ar", "target_arch = \"{}\""),             // Target architecture predicate
-    ("tf", "target_feature = \"{}\""),          // Target feature predicate
-    ("os", "target_os = \"{}\""),               // Target os predicate
-    ("fm", "target_family = \"{}\""),           // Target family predicate
-    ("ev", "target_env = \"{}\""),              // Target environment predicate
-    ("ed", "target_endian = \"{}\""),           // Target endian predicate
-    ("pw", "target_pointer_width = \"{}\""),    // Target pointer width predicate
-    ("vn", "target_vendor = \"{}\""),           // Target vendor predicate
-    ("at", "target_has_atomic = \"{}\""),       // Target has atomic predicate
-    ("pn", "panic = \"{}\""),                   // Panic predicate
-    ("ft", "feature = \"{}\""),                 // Feature predicate
-    ("_", PREDICATE_PLACEHOLDER)                // Wildcard predicate
-];
+const CFG_BOOST_FORCE : &str = "CFG_BOOST_FORCE";                   // Labels of arms to activate, separated by `,`.
+const CFG_BOOST_DISABLE : &str = "CFG_BOOST_DISABLE";               // Labels of arms to deactivate, separated by `,`.
+const CFG_BOOST_TRACE : &str = "CFG_BOOST_TRACE";                   // Trace all arms if set.
+const CFG_BOOST_DUMP : &str = "CFG_BOOST_DUMP";                     // Dump all expansions if set.
+const CFG_BOOST_REPORT_DIR : &str = "CFG_BOOST_REPORT_DIR";         // Directory of JSON expansion reports.
+const CFG_BOOST_EXPLAIN : &str = "CFG_BOOST_EXPLAIN";               // Target triple or cfg file traced arms are explained for.
+const TRACKED_ENV : [&str; 6] = [CFG_BOOST_FORCE, CFG_BOOST_DISABLE, CFG_BOOST_TRACE, CFG_BOOST_DUMP, CFG_BOOST_REPORT_DIR, CFG_BOOST_EXPLAIN];   // Environment variables read by expansions.
+const LABELS_SEPARATOR : char = ',';                                // Labels separator in environment variables.
+const FILE_KEY_AUTODOC : &str = "autodoc";                          // Configuration file key for autodocumentation.
+const FILE_KEY_AVAILABILITY_NOTES : &str = "availability_notes";    // Configuration file key for availability notes.
+const FILE_KEY_DOC_STUB : &str = "doc_stub";                        // Configuration file key for doc-stub mode.
+const FILE_KEY_MODIFIER_BEHAVIOUR : &str = "release_modifier_behaviour";    // Configuration file key for release modifier behaviour.
+const FILE_KEY_ALIASES : &str = "aliases";                          // Configuration file aliases table
+
+thread_local! {
+    /// Configuration cache with the manifest directory and configuration file it was loaded from.
+    static CONFIG_CACHE : RefCell<Option<(ConfigSource, Rc<Settings>)>> = const { RefCell::new(None) };
+}
+
+/// Manifest directory and explicit configuration file a configuration is loaded from.
+type ConfigSource = (Option<String>, Option<String>);
+
+/// Get the configuration of the crate being compiled, loaded by [Settings::load_from].
+/// 
+/// Configuration is cached and only reloaded if the crate root folder or configuration file changed.
+/// 
+/// Panic(s)
+/// Will panic! if `cfg_boost.toml` is malformed or if file given by `CFG_BOOST_CONFIG` is missing or malformed.
+pub(crate) fn get_config() -> Rc<Settings> {
+    let source : ConfigSource = (env::var(CARGO_MANIFEST_DIR).ok(), env::var(CFG_BOOST_CONFIG).ok());
+
+    CONFIG_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+
+        match cache.as_ref() {
+            Some((cached, config)) if cached.eq(&source) => config.clone(),
+            _ => {
+                let config = match Settings::load_from(source.0.as_ref().map(Path::new), source.1.as_ref().map(Path::new)) {
+                    Ok(config) => Rc::new(config),
+                    Err(ConfigError::Malformed(path, line)) => panic!("{}", CfgBoostError::InvalidConfigFile(path.display().to_string(), line).message("")),
+                    Err(ConfigError::NotFound(path)) => panic!("{}", CfgBoostError::ConfigFileNotFound(path.display().to_string()).message("")),
+                };
+                *cache = Some((source, config.clone()));
+                config
+            },
+        }
+    })
+}
+
+/// Generate tokenstream that make every configuration file and environment switch a dependency of the expansion.
+/// 
+/// Each file is included with a hidden `include_bytes!` so that editing it recompiles the crate.
+/// Files that don't exist yet (like a future `cfg_boost.toml`) cannot be tracked.
+/// Environment variables like `CFG_BOOST_FORCE` are read with a hidden `option_env!`.
+/// 
+/// Generated items are module or block items and cannot be used inside an `impl` or `trait`. There, they are inserted
+/// in function bodies or constant initializers instead with `syntax::insert_in_body`.
+pub(crate) fn generate_tracking_ts() -> TokenStream {
+    let mut ts : TokenStream = get_config().files().iter().map(|path| {
+        format!("const _ : &[u8] = include_bytes!({});", Literal::string(&path.display().to_string()))
+            .parse::<TokenStream>().unwrap()
+    }).collect();
+
+    ts.extend(TRACKED_ENV.iter().map(|key| format!("const _ : Option<&str> = option_env!(\"{}\");", key).parse::<TokenStream>().unwrap()));
+    ts
+}
+
+/// Get a setting from configuration files, then from environment variables.
+fn get_setting(file_key : &str, env_key : &str) -> Option<String> {
+    get_config().setting(file_key, env_key)
+}
 
 pub(crate) enum ReleaseModifierBehaviour {
     /// Panic! when trying to use modifiers on releae
@@ -56,24 +93,27 @@ pub(crate) enum ReleaseModifierBehaviour {
 
     /// Ignore modifiers on release
     Ignore,
+
+    /// Ignore modifiers on release with a compilation warning
+    Warn,
 }
 
 /// Get the modifier behaviour on release.
 /// 
 /// Modifiers are usually used for quick testing and create a different debug behaviour vs release.
 /// By default, panic is used so user know he forgot some modifier. This behaviour can be changed
-/// in config.toml to just ignore the modifiers and not panic. This must be done manually so the user
+/// in configuration to just ignore the modifiers, with or without warning, and not panic. This must be done manually so the user
 /// can acknowledge the risk.
-#[allow(dead_code)]
 pub(crate) fn get_release_modifier_behaviour() -> ReleaseModifierBehaviour{
 
-    match std::env::var(MODIFIER_BEHAVIOUR_KEY) {
-        Ok(value) => match value.as_str() {
+    match get_setting(FILE_KEY_MODIFIER_BEHAVIOUR, MODIFIER_BEHAVIOUR_KEY) {
+        Some(value) => match value.as_str() {
             "panic" => ReleaseModifierBehaviour::Panic,
             "ignore" => ReleaseModifierBehaviour::Ignore,
+            "warn" => ReleaseModifierBehaviour::Warn,
             _ => ReleaseModifierBehaviour::Panic,  // Any other value is considered panic.
         },
-        Err(_) => ReleaseModifierBehaviour::Panic,     // If not set, return panic as default
+        None => ReleaseModifierBehaviour::Panic,     // If not set, return panic as default
     }
 
 }
@@ -83,111 +123,134 @@ pub(crate) fn get_release_modifier_behaviour() -> ReleaseModifierBehaviour{
 /// If not set, default is true.
 #[inline(always)]
 pub(crate) fn is_cfg_boost_autodoc() -> bool {
-    match std::env::var(AUTO_DOC_KEY) {
-        Ok(value) => match value.as_str() {
+    match get_setting(FILE_KEY_AUTODOC, AUTO_DOC_KEY) {
+        Some(value) => match value.as_str() {
             "true" => true,
             "false" => false,
             _ => true,  // Any other value is considered true.
         },
-        Err(_) => true,     // If not set, return true as default
+        None => true,     // If not set, return true as default
     }
 }
 
 
-/// Returns True if cfg-attr is generated for documentation labels.
+/// Get if availability notes are added to documentation.
+/// 
+/// If not set, default is false.
 #[inline(always)]
-pub(crate) fn if_docsrs_enabled() -> bool {
-    // 1. Get previous result from cache. 
-    match env::var(CFG_BOOST_CARGO_CACHE) {
-        Ok(value) => {
-            value.eq("true")
-        },
-        Err(_) => {
-            // 2. Read Cargo.toml if no result
-            let str_path =  format!("{}/{}", env::var(CARGO_MANIFEST_DIR).unwrap(), CARGO_MANIFEST_NAME);
-            let file_path = Path::new(&str_path);
-
-            match fs::read_to_string(file_path){
-                Ok(content) => {
-                    match content.find(CFG_BOOST_DOCRS_TAG){
-                        Some(_) => { 
-                            env::set_var(CFG_BOOST_CARGO_CACHE, "true");    // Cache result
-                            true
-                        },
-                        None => {
-                            env::set_var(CFG_BOOST_CARGO_CACHE, "false");    // Cache result
-                            false
-                        },
-                    }
-                },
-
-                // Cargo.toml not found, return false.
-                Err(_) => {
-                    env::set_var(CFG_BOOST_CARGO_CACHE, "false");
-                    false
-                },
-            }
+pub(crate) fn is_cfg_boost_availability_notes() -> bool {
+    match get_setting(FILE_KEY_AVAILABILITY_NOTES, AVAILABILITY_NOTES_KEY) {
+        Some(value) => value.eq("true"),
+        None => false,
+    }
+}
+
+/// Get if autodoc use doc-stub mode.
+/// 
+/// In that mode, items of non-matching arms are documented with stub copies instead of compiling them for `doc`.
+/// If not set, default is false.
+#[inline(always)]
+pub(crate) fn is_cfg_boost_doc_stub() -> bool {
+    match get_setting(FILE_KEY_DOC_STUB, DOC_STUB_KEY) {
+        Some(value) => value.eq("true"),
+        None => false,
+    }
+}
+
+/// Returns the cfg name used in `#[cfg_attr(name, doc(cfg()))]` documentation tags or None if tags are not generated.
+#[inline(always)]
+pub(crate) fn get_docsrs_cfg() -> Option<String> {
+    get_config().docsrs_cfg()
+}
+
+/// Returns the cfg name set for docs.rs tags or `docsrs` by default.
+#[inline(always)]
+pub(crate) fn get_docsrs_cfg_name() -> String {
+    get_config().docsrs_cfg_name()
+}
+
+
+/// Get aliases set in configuration files and `cfg_boost-*` environment variables, without duplicates.
+pub(crate) fn get_cfg_boost_aliases() -> Vec<String> {
+    let mut aliases : Vec<String> = get_config().table(FILE_KEY_ALIASES).iter().map(|(alias, _)| String::from(*alias)).collect();
+
+    for (key, _) in env::vars() {
+        match key.strip_prefix(ENV_KEY_ALIAS) {
+            Some(alias) if !aliases.iter().any(|a| a.eq(alias)) => aliases.push(String::from(alias)),
+            _ => {},
         }
     }
+
+    aliases
+}
+
+/// Get features of crate Cargo.toml, including optional dependencies.
+#[inline(always)]
+pub(crate) fn get_cfg_boost_features() -> Vec<String> {
+    get_config().features().map(<[String]>::to_vec).unwrap_or_default()
+}
+
+/// Get labels of arms forced on with `CFG_BOOST_FORCE` environment variable.
+#[inline(always)]
+pub(crate) fn get_cfg_boost_forced_labels() -> Vec<String> {
+    get_labels(CFG_BOOST_FORCE)
 }
 
+/// Get labels of arms forced off with `CFG_BOOST_DISABLE` environment variable.
+#[inline(always)]
+pub(crate) fn get_cfg_boost_disabled_labels() -> Vec<String> {
+    get_labels(CFG_BOOST_DISABLE)
+}
 
-/// Parse tokens to generate configuration predicate.
+/// Get if every arm is traced with `CFG_BOOST_TRACE` environment variable.
 /// 
-/// Error(s)
-/// Returns Err([SyntaxParseError::InvalidConfigurationPredicate]) if predicate not defined.
+/// Any value except `0` and `false` enable trace.
 #[inline(always)]
-pub fn get_cfg_boost_predicate(tokens : &str) -> Result<String, CfgBoostError> {
-
-    // 1. Extract label and predicate from tokens
-    match tokens.find(":") {
-        Some(position) => {
-            let label = tokens[0..position].trim();
-            let cfg_opt = tokens[position + 1..].trim();
-
-            // 2. Try to match environment variable to see if predicate was defined in config.toml.
-            match env::var(format!("{}{}", ENV_KEY_PREDICATE, cfg_opt)) {
-                Ok(cfg_value) => Ok(String::from(cfg_value.replace(PREDICATE_PLACEHOLDER, label))),
-                Err(_) =>  {
-                    // 3. Find predefined predicates
-                    match PREDICATES.iter().find(|p| p.0.eq(cfg_opt)){
-                        // Predicate found, return value
-                        Some(pred) =>  Ok(String::from(pred.1.replace(PREDICATE_PLACEHOLDER, label))),
-
-                        // Not found, raise error.
-                        None => Err(CfgBoostError::InvalidConfigurationPredicate(String::from(cfg_opt))),
-                    }
-                },
-            }
-        },
+pub(crate) fn is_cfg_boost_trace() -> bool {
+    is_env_switch_on(CFG_BOOST_TRACE)
+}
+
+/// Get if every expansion is dumped with `CFG_BOOST_DUMP` environment variable.
+/// 
+/// Any value except `0` and `false` enable dump.
+#[inline(always)]
+pub(crate) fn is_cfg_boost_dump() -> bool {
+    is_env_switch_on(CFG_BOOST_DUMP)
+}
 
-        // Should never happen but good to have in hand
-        None => Err(CfgBoostError::InvalidConfigurationPredicate(String::from(tokens))),
-    } 
+/// Get directory where JSON expansion reports are written with `CFG_BOOST_REPORT_DIR` environment variable.
+#[inline(always)]
+pub(crate) fn get_cfg_boost_report_dir() -> Option<PathBuf> {
+    match env::var(CFG_BOOST_REPORT_DIR) {
+        Ok(dir) if !dir.is_empty() => Some(PathBuf::from(dir)),
+        _ => None,
+    }
+}
 
+/// Get target triple or cfg file traced arms are explained for with `CFG_BOOST_EXPLAIN` environment variable.
+#[inline(always)]
+pub(crate) fn get_cfg_boost_explain_target() -> Option<String> {
+    match env::var(CFG_BOOST_EXPLAIN) {
+        Ok(target) if !target.trim().is_empty() => Some(String::from(target.trim())),
+        _ => None,
+    }
 }
 
+/// Returns true if environment variable is set to any value except `0` and `false`.
+#[inline(always)]
+fn is_env_switch_on(env_key : &str) -> bool {
+    match env::var(env_key) {
+        Ok(value) => !value.eq("0") && !value.eq("false"),
+        Err(_) => false,
+    }
+}
 
-/// Parse label to generate alias content.
-/// 
-/// Error(s)
-/// Returns Err([TargetCfgError::AliasNotFound]) if alias not defined.
+/// Get labels separated by `,` in environment variable.
 #[inline(always)]
-pub fn get_cfg_boost_alias(label : &str) -> Result<String, CfgBoostError> {
-
-    // 1. Try to match environment variable to see if it was defined in config.toml.
-    match env::var(format!("{}{}", ENV_KEY_ALIAS, label)) {
-        Ok(alias) => Ok(alias.clone()),     
-        Err(_e) => {
-            // 2. Find predefined alias
-            match ALIASES.iter().find(|a| a.0.eq(label)){
-                // Alias found, return value
-                Some(alias) => Ok(String::from(alias.1)),
-
-                // Not found, raise error.
-                None => Err(CfgBoostError::AliasNotFound(String::from(label))),
-            }
-        },
+fn get_labels(env_key : &str) -> Vec<String> {
+    match env::var(env_key) {
+        Ok(labels) => labels.split(LABELS_SEPARATOR).map(|label| String::from(label.trim().trim_start_matches('\''))).filter(|label| !label.is_empty()).collect(),
+        Err(_) => Vec::new(),
     }
+}
 
-}
\ No newline at end of file
@@ -1,22 +1,7 @@
-use crate::arm::{ARM_SEPARATOR, CONTENT_SEPARATOR_0, CONTENT_SEPARATOR_1, WILDCARD_ARM, MODIFIER_ACTIVATE, MODIFIER_DEACTIVATE, MODIFIER_PANIC};
+use crate::{arm::{ARM_SEPARATOR, CONTENT_SEPARATOR_0, CONTENT_SEPARATOR_1, WILDCARD_ARM, MODIFIER_ACTIVATE, MODIFIER_DEACTIVATE, MODIFIER_PANIC, MODIFIER_TRACE}, options::macro_options};
 
 /// Possible cfg_boost errors.
 pub enum CfgBoostError {
-    /// Missing operator (happens when a leaf contains a space)
-    MissingOperator,
-
-    /// Empty node due to missing variable.
-    EmptyNode,
-
-    /// Invalid character used
-    InvalidCharacter(String),
-
-    /// Alias written is not found
-    AliasNotFound(String),
-
-    /// Invalid configuration predicate
-    InvalidConfigurationPredicate(String),
-
     /// Happens when having an empty arm.
     EmptyArm,
 
@@ -47,29 +32,62 @@ pub enum CfgBoostError {
     /// Happens when a separator `=>` is missing between arms.
     ContentSeparatorMissing,
 
-    /// Happens when a modifier `+` or `-` isn't the first character of arm.
+    /// Happens when a modifier `+`, `-`, `@` or `?` isn't the first character of arm.
     ModifierNotFirst,
 
     /// Happens when a modifier `+` or `-` is used during release compilation and not set to ignore.
-    #[allow(dead_code)]
     ModifierPanicRelease,
 
+    /// Warning when a modifier `+` or `-` is used during release compilation and set to warn.
+    ModifierWarnRelease,
+
     /// Happens when more than 1 modifier `+` in match_cfg!.
     MatchModifierMoreThanOneActivate,
 
     /// Happens when using modifier `-` on wildcard arm of match_cfg!.
     MatchDeactivatedWildArm,
+
+    /// Happens when a configuration file cannot be parsed.
+    InvalidConfigFile(String, usize),
+
+    /// Happens when configuration file given by `CFG_BOOST_CONFIG` is not found.
+    ConfigFileNotFound(String),
+
+    /// Happens when an arm label `'label:` is incorrect.
+    LabelSyntaxError,
+
+    /// Happens when an arm label is both in `CFG_BOOST_FORCE` and `CFG_BOOST_DISABLE`.
+    LabelForcedAndDisabled(String),
+
+    /// Happens when a JSON report cannot be written in `CFG_BOOST_REPORT_DIR`.
+    ReportNotWritten(String),
+
+    /// Happens when cfg set of `CFG_BOOST_EXPLAIN` target cannot be read.
+    ExplainTargetNotFound(String),
+
+    /// Happens when an option of macro header `#![]` is unknown or doesn't apply to macro.
+    InvalidOption(String),
+
+    /// Happens when an option value of macro header `#![]` isn't `true` or `false`.
+    InvalidOptionValue(String),
+
+    /// Happens when a predicate of match_target! cannot be checked with environment variables.
+    RuntimePredicate,
+
+    /// Happens when if_cfg! branches are malformed.
+    IfCfgSyntaxError,
+
+    /// Happens when an alias listed by cfg_boost_active! isn't an identifier or cannot be resolved.
+    InvalidAlias(String),
+
+    /// Happens when release modifier warning cannot be generated in an arm inside an impl or trait.
+    ModifierWarnNoBody,
 }
 
 /// Error message implementation.
 impl CfgBoostError {
     pub fn message(&self, tokens : &str) -> String {
         match self {
-            CfgBoostError::MissingOperator => format!("Operator `&` or '|' missing for `{:?}`. Target must not contain space.", tokens),
-            CfgBoostError::EmptyNode =>  format!("Empty node generated from attributes. Are you missing a statement between separator?"),
-            CfgBoostError::InvalidCharacter(c) => format!("Invalid character `{}` for `{:?}`.", c, tokens),
-            CfgBoostError::AliasNotFound(alias) => format!("Alias `{}` has no match! Is it added in config.toml as `target_cfg-{}`?", alias, alias),
-            CfgBoostError::InvalidConfigurationPredicate(cfg_prd) => format!("Configuration predicate `{}` has no match! Is it added in config.toml as `target_cfg_predicate-{}`?", cfg_prd, cfg_prd),
             CfgBoostError::EmptyArm => format!("Empty arm with no attributes detected!"),
             CfgBoostError::WildcardArmNotLast => format!("Wildcard branch `_` must ALWAYS be the last branch."),
             CfgBoostError::ArmSeparatorMissing => format!("Arm syntax incorrect. Are you missing a separator `{}` between arms?", ARM_SEPARATOR),
@@ -80,10 +98,23 @@ impl CfgBoostError {
             CfgBoostError::LegacySyntaxError => format!("Legacy syntax error in `{}`.", tokens),
             CfgBoostError::MixedSyntaxError => format!("Legacy syntax and simplified syntax can't be mixed on same arm!"),
             CfgBoostError::ContentSeparatorMissing => format!("Arm content separator `{}{}` missing!", CONTENT_SEPARATOR_0, CONTENT_SEPARATOR_1),
-            CfgBoostError::ModifierNotFirst => format!("Arm modifiers `{}`, `{}` and `{}` must be the first character of arm!", MODIFIER_ACTIVATE, MODIFIER_DEACTIVATE, MODIFIER_PANIC),
+            CfgBoostError::ModifierNotFirst => format!("Arm modifiers `{}`, `{}`, `{}` and `{}` must be the first character of arm!", MODIFIER_ACTIVATE, MODIFIER_DEACTIVATE, MODIFIER_PANIC, MODIFIER_TRACE),
             CfgBoostError::ModifierPanicRelease => format!("Arm modifiers `{}` and `{}` will panic during release compilation by default! This behaviour can be changed. See https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax#six-modifiers", MODIFIER_ACTIVATE, MODIFIER_DEACTIVATE),
+            CfgBoostError::ModifierWarnRelease => format!("Arm modifiers `{}` and `{}` are ignored during release compilation!", MODIFIER_ACTIVATE, MODIFIER_DEACTIVATE),
             CfgBoostError::MatchModifierMoreThanOneActivate => format!("match_cfg! cannot have more than one `{}` modifier!", MODIFIER_ACTIVATE),
             CfgBoostError::MatchDeactivatedWildArm => format!("match_cfg! cannot deactivate wildcard arm with `{}` modifier!", MODIFIER_DEACTIVATE),
+            CfgBoostError::InvalidConfigFile(path, line) => format!("Configuration file `{}` is malformed at line {}!", path, line),
+            CfgBoostError::ConfigFileNotFound(path) => format!("Configuration file `{}` set with `CFG_BOOST_CONFIG` not found!", path),
+            CfgBoostError::LabelSyntaxError => format!("Arm label syntax error in `{}`. Labels are written `'label:` before arm predicates.", tokens),
+            CfgBoostError::LabelForcedAndDisabled(label) => format!("Arm label `{}` cannot be in both `CFG_BOOST_FORCE` and `CFG_BOOST_DISABLE`!", label),
+            CfgBoostError::ReportNotWritten(path) => format!("Expansion report `{}` cannot be written! Is `CFG_BOOST_REPORT_DIR` a writable directory?", path),
+            CfgBoostError::ExplainTargetNotFound(target) => format!("Cfg set of `{}` set with `CFG_BOOST_EXPLAIN` cannot be read! Use a target triple of `rustc --print target-list` or a file written by `rustc --print cfg`.", target),
+            CfgBoostError::InvalidOption(option) => format!("Invalid option `{}` in {} header! Options of {} are `{}`.", option, tokens, tokens, macro_options(tokens).join("`, `")),
+            CfgBoostError::InvalidOptionValue(option) => format!("Option `{}` value must be `true` or `false`, not `{}`!", option, tokens),
+            CfgBoostError::RuntimePredicate => format!("Predicate `{}` cannot be checked at runtime by match_target!", tokens),
+            CfgBoostError::IfCfgSyntaxError => format!("if_cfg! syntax error in `{}`. Branches are written `predicates {{ .. }} else if predicates {{ .. }} else {{ .. }}`.", tokens),
+            CfgBoostError::InvalidAlias(alias) => format!("Alias `{}` cannot be listed by cfg_boost_active! {}", alias, tokens),
+            CfgBoostError::ModifierWarnNoBody => format!("Arm modifiers `{}` and `{}` cannot warn during release compilation in arms of {} without a function body or constant inside an impl or trait! Add a function or use `panic` or `ignore` release behaviour.", MODIFIER_ACTIVATE, MODIFIER_DEACTIVATE, tokens),
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,134 @@
+// Explanation of arms predicates evaluated against the cfg set of a target given by `CFG_BOOST_EXPLAIN`.
+
+use std::{env, fs, path::PathBuf};
+
+use proc_macro::{TokenStream, TokenTree};
+
+use cfg_boost_parser::{CfgSet, SyntaxTreeNode, CARGO_MANIFEST_DIR, CFG_ANY, CFG_ALL, CFG_NOT};
+
+use crate::{arm::TargetArm, config::get_cfg_boost_explain_target, errors::CfgBoostError, syntax::to_tokens};
+
+/// Cfg attribute keyword
+const CFG_ATTRIBUTE : &str = "cfg";
+
+/// Explanation indentation
+const EXPLAIN_INDENT : &str = "    ";
+
+/// Explainer of arms for the cfg set of a target.
+pub(crate) struct Explainer {
+    /// Target triple or cfg file
+    target : String,
+
+    /// Cfg set of target
+    cfg_set : CfgSet,
+}
+
+impl Explainer {
+    /// Get explainer of target set with `CFG_BOOST_EXPLAIN`, if any.
+    ///
+    /// `CFG_BOOST_EXPLAIN` is either a file written by `rustc --print cfg` or a target triple given to `rustc --print cfg --target`.
+    ///
+    /// Panic(s)
+    /// Will panic! if cfg set cannot be read.
+    pub fn from_env() -> Option<Explainer> {
+
+        let target = get_cfg_boost_explain_target()?;
+
+        // 1. Read cfg file if it exists, relative to manifest directory.
+        let path = match env::var(CARGO_MANIFEST_DIR) {
+            Ok(dir) => PathBuf::from(dir).join(&target),
+            Err(_) => PathBuf::from(&target),
+        };
+
+        let cfg_set = if path.is_file() {
+            fs::read_to_string(&path).ok().map(|cfg| CfgSet::parse(&cfg))
+        } else {
+            // 2. Else get cfg of target triple from rustc.
+            CfgSet::from_target(&target)
+        };
+
+        match cfg_set {
+            Some(cfg_set) => Some(Explainer { target, cfg_set }),
+            None => panic!("{}", CfgBoostError::ExplainTargetNotFound(target).message("")),
+        }
+
+    }
+
+    /// Explain arm by evaluating its predicates tree leaf by leaf and its generated #[cfg].
+    pub fn explain(&self, arm : &TargetArm) -> String {
+
+        let mut explanation = format!("Explained for `{}` :\n", self.target);
+
+        // 1. Tree leaf by leaf
+        if let Some(tree) = &arm.tree {
+            self.explain_node(tree, 0, &mut explanation);
+        }
+
+        // 2. Generated #[cfg] with modifiers and exclusion of previous arms
+        let cfg = Self::cfg_predicate(arm.cfg_ts.clone());
+        explanation.push_str(&format!("Generated `{}` is `{}`\n", cfg.to_string().split_whitespace().collect::<Vec<&str>>().join(" "), self.cfg_set.eval(&to_tokens(cfg))));
+
+        explanation
+
+    }
+
+    /// Write evaluation of node and its children with indentation. Returns node value.
+    fn explain_node(&self, node : &SyntaxTreeNode, depth : usize, explanation : &mut String) -> bool {
+
+        let indent = EXPLAIN_INDENT.repeat(depth);
+        let position = explanation.len();
+
+        let (value, line) = match node {
+            SyntaxTreeNode::NOT(child) => (!self.explain_node(child, depth + 1, explanation), String::from(CFG_NOT)),
+            SyntaxTreeNode::ANY(left, right) => {
+                let left = self.explain_node(left, depth + 1, explanation);
+                (self.explain_node(right, depth + 1, explanation) || left, String::from(CFG_ANY))
+            },
+            SyntaxTreeNode::ALL(left, right) => {
+                let left = self.explain_node(left, depth + 1, explanation);
+                (self.explain_node(right, depth + 1, explanation) && left, String::from(CFG_ALL))
+            },
+            SyntaxTreeNode::Alias(label, child) => (self.explain_node(child, depth + 1, explanation), format!("alias `{}`", label)),
+            SyntaxTreeNode::LEAF(label, predicate) => {
+                let value = self.cfg_set.eval_str(predicate);
+                let label = label.split(':').next().unwrap_or_default().trim();
+                (value, format!("`{}` => `{}`{}", label, predicate, if value { String::new() } else { self.why_false(predicate) }))
+            },
+        };
+
+        // Parent is written before its children
+        explanation.insert_str(position, &format!("{}[{}] {}\n", indent, value, line));
+        value
+
+    }
+
+    /// Tell which values the target has for a false `key = "value"` predicate.
+    fn why_false(&self, predicate : &str) -> String {
+        match predicate.split_once('=') {
+            Some((key, _)) => {
+                let values : Vec<String> = self.cfg_set.values(key.trim()).iter().map(|v| format!("\"{}\"", v)).collect();
+                if values.is_empty() {
+                    format!(" (target has no `{}`)", key.trim())
+                } else {
+                    format!(" (target has `{}` = {})", key.trim(), values.join(", "))
+                }
+            },
+            None => String::from(" (not in target cfg set)"),
+        }
+    }
+
+    /// Get cfg predicate inside `#[cfg()]` tokens.
+    fn cfg_predicate(cfg_ts : TokenStream) -> TokenStream {
+        for token in cfg_ts {
+            if let TokenTree::Group(group) = token {
+                let mut tokens = group.stream().into_iter();
+                if let (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(pred))) = (tokens.next(), tokens.next()) {
+                    if ident.to_string().eq(CFG_ATTRIBUTE) {
+                        return pred.stream();
+                    }
+                }
+            }
+        }
+        TokenStream::new()
+    }
+}
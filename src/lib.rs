@@ -54,7 +54,11 @@
 //! 
 //! [Get more examples on the wiki.](https://github.com/NickelAngeStudio/cfg_boost/wiki/Examples)
 use arm::TargetArm;
-use proc_macro::{TokenStream, TokenTree, Group, Delimiter};
+use config::generate_tracking_ts;
+use arm::TargetArmType;
+use proc_macro::{TokenStream, TokenTree, Group, Delimiter, Literal};
+use cfg_boost_parser::{Token, SyntaxTreeNode};
+use errors::CfgBoostError;
 
 /// Errors enumeration
 mod errors;
@@ -65,9 +69,21 @@ mod config;
 /// Arms structure and functions
 mod arm;
 
-/// Syntax tree
+/// Tokenstream helpers
 mod syntax;
 
+/// Macro options header
+mod options;
+
+/// JSON expansion reports
+mod report;
+
+/// Arms explanation for a target
+mod explain;
+
+/// Runtime checks for build scripts
+mod runtime;
+
 /// Proc macro source enumeration to determinate matching macro source.
 #[derive(Clone, Copy)]
 pub(crate) enum CfgBoostMacroSource {
@@ -78,6 +94,48 @@ pub(crate) enum CfgBoostMacroSource {
     MatchMacro,
 }
 
+/// Macros names used in dumps and reports
+pub(crate) const TARGET_CFG_NAME : &str = "target_cfg!";
+pub(crate) const MATCH_CFG_NAME : &str = "match_cfg!";
+pub(crate) const META_CFG_NAME : &str = "meta_cfg";
+pub(crate) const MATCH_TARGET_NAME : &str = "match_target!";
+pub(crate) const CFG_LABEL_NAME : &str = "cfg_label!";
+pub(crate) const CFG_PREDICATE_NAME : &str = "cfg_predicate!";
+
+/// Print pretty-printed generated items of a macro to stderr, if any.
+fn dump_expansion(macro_name : &str, dump : &[String]) {
+    if !dump.is_empty() {
+        eprintln!("\n*** {} expansion dumped by `dump` option or `CFG_BOOST_DUMP` ***\n{}", macro_name, dump.join("\n"));
+    }
+}
+
+/// Generate `if .. {} else if .. {} else {}` chain of match arms, with condition generated from arm predicates and modifier.
+/// 
+/// Wildcard arm is always last and becomes the else block.
+fn generate_arms_chain(arms : &[TargetArm], condition : impl Fn(TokenStream) -> String) -> TokenStream {
+
+    let mut chain = TokenStream::new();
+
+    for arm in arms.iter() {
+        // 1. Add condition, except for wildcard arm
+        if !matches!(arm.arm_type, TargetArmType::Wildcard) {
+            let predicate = TargetArm::apply_modifier(arm.pred_ts.clone(), arm.modifier).parse::<TokenStream>().unwrap();
+            chain.extend(format!("if {}", condition(predicate)).parse::<TokenStream>().unwrap());
+        }
+
+        // 2. Add braced content
+        chain.extend(TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace, arm.content.clone()))));
+
+        // 3. Chain next arm
+        if !matches!(arm.arm_type, TargetArmType::Wildcard) {
+            chain.extend("else".parse::<TokenStream>().unwrap());
+        }
+    }
+
+    chain
+
+}
+
 /// Procedural macro used to declare resource and item outside function.
 /// 
 /// ## Description
@@ -93,16 +151,21 @@ pub(crate) enum CfgBoostMacroSource {
 /// ## Syntax
 /// ```ignore
 /// target_cfg!{
-///     !? alias* (| &)? !? value:pred* => {},+
+///     #![option(true|false),*]?    // Optional options header
+///     ('label:)? !? alias* (| &)? !? value:pred* => {},+
 ///     #[cfg(legacy_syntax)] => {},+    // target_cfg! also support legacy syntax
 /// }
 /// ```
 /// [More details on syntax here.](https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax)
 /// 
+/// Labeled arms can be forced on or off without editing code with `CFG_BOOST_FORCE=label` and `CFG_BOOST_DISABLE=label` environment variables.
+/// 
 /// ## Documentation
 /// target_cfg! always wrap arm with `doc | (arm)` if `doc` is not defined in the arm (even for legacy syntax). This allow `cargo doc` to always generate documentation of each arm. 
 /// This feature can be deactivated. [More details here](https://github.com/NickelAngeStudio/cfg_boost/wiki/Documentation)
 /// 
+/// Options `autodoc`, `docsrs_tag`, `doc_stub`, `availability_notes` and `dump` can be changed for one invocation with a header like `#![autodoc(false), docsrs_tag(false)]`.
+/// 
 /// **BONUS :** target_cfg! can also generate those dependency tags. 
 /// <img src="https://github.com/NickelAngeStudio/cfg_boost/raw/main/img/tag.png?raw=true" width="600" height="160"><br>
 /// [More details here](https://github.com/NickelAngeStudio/cfg_boost/wiki/Documentation)
@@ -157,33 +220,96 @@ pub(crate) enum CfgBoostMacroSource {
 /// [More examples here.](https://github.com/NickelAngeStudio/cfg_boost/wiki/Examples)
 #[proc_macro]
 pub fn target_cfg(item: TokenStream) -> TokenStream {
+    generate_target_cfg(item, TARGET_CFG_NAME)
+}
+
+/// Generate target_cfg! content for macro_name, which is reported in expansion reports.
+fn generate_target_cfg(item: TokenStream, macro_name : &str) -> TokenStream {
 
     // TokenStream that accumulate content
     let mut content = TokenStream::new();
 
+    // Pretty-printed items for dump
+    let mut dump : Vec<String> = Vec::new();
+
     // 1. Extract target arms
-    let arms = TargetArm::extract(item.clone(), CfgBoostMacroSource::TargetMacro);
+    let arms = TargetArm::extract(item.clone(), CfgBoostMacroSource::TargetMacro, macro_name);
 
-    // 2. For each arm
-    for arm in arms {
+    // 2. Split arms content into vectors of items
+    let arms_items : Vec<Vec<TokenStream>> = arms.iter().map(|arm| syntax::split_items(arm.content.clone())).collect();
 
-        // 2.1. Split item into vector of items
-        let items = syntax::split_items(arm.content.clone());
+    // Flag set when items can only be in a module or block. Otherwise, tracking and release warning are inserted in a body of each arm.
+    let module_level = arms_items.iter().any(|items| syntax::has_module_item(items));
+    let mut body_ts = TokenStream::new();
+    if !module_level {
+        body_ts.extend(generate_tracking_ts());
+        body_ts.extend(TargetArm::generate_release_warning_ts(&arms));
+    }
 
-        // 2.2. For each item in vector of items
+    // 3. For each arm items
+    for (index, items) in arms_items.iter().enumerate() {
+
+        // Flag set when tracking still needs a body in this arm.
+        let mut untracked = !module_level;
+
+        // 3.1. For each item in vector of items
         for item in items {
-            // 2.2.1. Add cfg header.
-            content.extend(arm.cfg_ts.clone()); 
+            // 3.1.1. Get arm generating item, documenting same-named items once.
+            let arm = TargetArm::item_arm(&arms, &arms_items, index, item);
+
+            // 3.1.2. Insert tracking in first item with a body of arm inside an impl or trait.
+            let mut tracked_item = item.clone();
+            let mut stub = syntax::stub_item(item.clone(), false);
+            if untracked {
+                if let Some(with_body) = syntax::insert_in_body(item.clone(), body_ts.clone()) {
+                    tracked_item = with_body;
+                    stub = stub.map(|stub| syntax::insert_in_body(stub.clone(), body_ts.clone()).unwrap_or(stub));
+                    untracked = false;
+                }
+            }
+
+            // 3.1.3. Add cfg header.
+            let mut item_ts = arm.cfg_ts.clone(); 
 
-            // 2.2.2. Add cfg_attr
-            content.extend(arm.attr_ts.clone());
+            // 3.1.4. Add cfg_attr
+            item_ts.extend(arm.attr_ts.clone());
 
-            // 2.2.3. Add item to content
-            content.extend(item);
+            // 3.1.5. Add item with availability note
+            item_ts.extend(syntax::insert_after_attributes(tracked_item, arm.note_ts.clone()));
+
+            // 3.1.6. Add doc-only stub copy of item in doc-stub mode, unless item is left out.
+            if let (false, Some(stub)) = (arm.stub_cfg_ts.is_empty(), stub) {
+                item_ts.extend(arm.stub_cfg_ts.clone());
+                item_ts.extend(arm.attr_ts.clone());
+                item_ts.extend(syntax::insert_after_attributes(stub, arm.note_ts.clone()));
+            }
+
+            // 3.1.7. Add item to content and dump
+            if arm.options.dump {
+                dump.push(syntax::pretty_print(item_ts.clone()));
+            }
+            content.extend(item_ts);
         }
+
+        // 3.2. Release warning cannot be lost in an arm without body, like an arm of `type` only.
+        if untracked && !TargetArm::generate_release_warning_ts(&arms).is_empty() {
+            panic!("{}", CfgBoostError::ModifierWarnNoBody.message(macro_name));
+        }
+    }
+
+    // 4. Track configuration files at module level. Inside an impl or trait, they are tracked in bodies of arms items.
+    if module_level {
+        content.extend(generate_tracking_ts());
     }
 
-    // 3. Return content.
+    // 5. Apply release modifier behaviour
+    content.extend(TargetArm::generate_release_modifier_ts(&arms, module_level));
+
+    // 6. Print dumped items and write report
+    dump_expansion(macro_name, &dump);
+    report::write_report(macro_name, &arms, &arms_items);
+
+    // 7. Return content.
     content
 
 }
@@ -200,10 +326,15 @@ pub fn target_cfg(item: TokenStream) -> TokenStream {
 /// 
 /// **match_cfg! has no runtime cost.**
 /// 
+/// Arms for other platforms are removed before type checking. When arms content is portable, the `checked` header option 
+/// lowers match_cfg! to an `if cfg!(..) {} else if cfg!(..) {} else {}` chain instead : every arm is type-checked on every platform
+/// and the optimizer removes arms that never match.
+/// 
 /// ## Syntax
 /// ```ignore
 /// match_cfg!{
-///     !? alias* (| &)? !? value:pred* => {},+
+///     #![dump, checked]?    // Optional options header
+///     ('label:)? !? alias* (| &)? !? value:pred* => {},+
 ///     #[cfg(legacy_syntax)] => {},+    // match_cfg! also support legacy syntax
 ///     _ => {}+?     // Mandatory wildcard arm
 /// };?
@@ -262,28 +393,389 @@ pub fn target_cfg(item: TokenStream) -> TokenStream {
 /// [More examples here.](https://github.com/NickelAngeStudio/cfg_boost/wiki/Examples)
 #[proc_macro]
 pub fn match_cfg(item: TokenStream) -> TokenStream {
+    generate_match_cfg(&TargetArm::extract(item, CfgBoostMacroSource::MatchMacro, MATCH_CFG_NAME), MATCH_CFG_NAME)
+}
+
+/// Generate match_cfg! content of extracted arms for macro_name, which is reported in expansion reports.
+fn generate_match_cfg(arms : &[TargetArm], macro_name : &str) -> TokenStream {
 
-     // TokenStream that accumulate content
-     let mut content = TokenStream::new();
+     // TokenStream that accumulate content, starting with configuration files tracking.
+     let mut content = generate_tracking_ts();
 
-     // 1. Extract target arms
-     let arms = TargetArm::extract(item.clone(), CfgBoostMacroSource::MatchMacro);
+     // 1. Apply release modifier behaviour
+     content.extend(TargetArm::generate_release_modifier_ts(arms, true));
  
-     // 2. For each arm
-     for arm in arms {
+     // Pretty-printed arms for dump
+     let mut dump : Vec<String> = Vec::new();
+
+     // 2. For each arm, unless in checked mode
+     for arm in arms.iter().filter(|arm| !arm.options.checked) {
          // 2.1. Add cfg header.
-         content.extend(arm.cfg_ts.clone()); 
+         let mut arm_ts = arm.cfg_ts.clone(); 
  
          // 2.2. Add braced content
-         content.extend(TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace, arm.content.clone()))));
+         arm_ts.extend(TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace, arm.content.clone()))));
+
+         // 2.3. Add arm to content and dump with exclusion of previous arms
+         if arm.options.dump {
+             dump.push(format!("// Exclusion : {}\n{}", arm.exclusion_ts, syntax::pretty_print(arm_ts.clone())));
+         }
+         content.extend(arm_ts);
+     }
+
+     // 3. In checked mode, generate `if cfg!(..) {} else if cfg!(..) {} else {}` chain type-checking every arm
+     if arms.iter().any(|arm| arm.options.checked) {
+         let chain = generate_arms_chain(arms, |predicate| format!("cfg!({})", predicate));
+         if arms.iter().any(|arm| arm.options.dump) {
+             dump.push(syntax::pretty_print(chain.clone()));
+         }
+         content.extend(chain);
      }
  
-     // 3. Add braces around content then return it.
+     // 4. Print dumped arms and write report
+     dump_expansion(macro_name, &dump);
+     report::write_report(macro_name, arms, &[]);
+ 
+     // 5. Add braces around content then return it.
      TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace,content)))
 
 }
 
 
+/// Procedural macro naming the first matching arm with a `&'static str`.
+/// 
+/// ## Description
+/// cfg_label! takes predicates separated by `,` and follows [match_cfg!] rules : the first matching predicates are selected and all possible values
+/// must be covered with a [wildcard](https://doc.rust-lang.org/reference/patterns.html#wildcard-pattern). It expands to the predicates as written, 
+/// like `"desktop"` or `"wasm:_"`, to the arm label if set or to `"_"` for the wildcard. Useful to tell which platform branch a binary was built with.
+/// 
+/// Use [cfg_predicate!] to get the resolved predicate instead.
+/// 
+/// **cfg_label! has no runtime cost.**
+/// 
+/// ## Syntax
+/// ```ignore
+/// cfg_label!{
+///     #![dump]?    // Optional options header
+///     ('label:)? !? alias* (| &)? !? value:pred*,+
+///     #[cfg(legacy_syntax)],+    // cfg_label! also support legacy syntax
+///     _     // Mandatory wildcard arm
+/// }
+/// ```
+/// 
+/// ## Example
+/// ```ignore
+/// let platform : &str = cfg_label!{ desktop, mobile, 'web: wasm:_, _ };     // "desktop", "mobile", "web" or "_"
+/// ```
+#[proc_macro]
+pub fn cfg_label(item: TokenStream) -> TokenStream {
+    generate_cfg_strings(item, CFG_LABEL_NAME, |arm| match (&arm.label, arm.arm_type) {
+        (Some(label), _) => label.clone(),
+        (None, TargetArmType::Wildcard) => String::from(arm::WILDCARD_ARM_STR),
+        (None, _) => syntax::source_text(arm.arm_ts.clone()),
+    })
+}
+
+
+/// Procedural macro giving the resolved predicate of the first matching arm as a `&'static str`.
+/// 
+/// ## Description
+/// cfg_predicate! works like [cfg_label!] but expands to the resolved predicate of the first matching arm, 
+/// like `"any(target_os = \"linux\", any(windows, target_os = \"macos\"))"` for `desktop`, or to `"_"` for the wildcard.
+/// 
+/// **cfg_predicate! has no runtime cost.**
+/// 
+/// ## Example
+/// ```ignore
+/// let predicate : &str = cfg_predicate!{ desktop, mobile, wasm:_, _ };
+/// ```
+#[proc_macro]
+pub fn cfg_predicate(item: TokenStream) -> TokenStream {
+    generate_cfg_strings(item, CFG_PREDICATE_NAME, |arm| match arm.arm_type {
+        TargetArmType::Wildcard => String::from(arm::WILDCARD_ARM_STR),
+        _ => cfg_boost_parser::to_source(&syntax::to_tokens(arm.pred_ts.clone())),
+    })
+}
+
+/// Generate match_cfg! content of predicates separated by `,` for macro_name, each arm content being the string written by arm_string.
+fn generate_cfg_strings(item: TokenStream, macro_name : &str, arm_string : impl Fn(&TargetArm) -> String) -> TokenStream {
+
+    // 1. Write each predicates as an arm with empty content, like `desktop => {},`
+    let mut stream = TokenStream::new();
+    let mut predicates = false;
+    for token in item {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == arm::ARM_SEPARATOR => {
+                stream.extend(format!("=> {{}}{}", arm::ARM_SEPARATOR).parse::<TokenStream>().unwrap());
+                predicates = false;
+            },
+            _ => {
+                stream.extend(TokenStream::from(token));
+                predicates = true;
+            },
+        }
+    }
+    if predicates {     // Last `,` is optional
+        stream.extend("=> {}".parse::<TokenStream>().unwrap());
+    }
+
+    // 2. Extract arms and set their content
+    let mut arms = TargetArm::extract(stream, CfgBoostMacroSource::MatchMacro, macro_name);
+    for arm in arms.iter_mut() {
+        arm.content = TokenStream::from(TokenTree::from(Literal::string(&arm_string(arm))));
+    }
+
+    // 3. Generate match_cfg! content
+    generate_match_cfg(&arms, macro_name)
+
+}
+
+
+/// Procedural macro listing aliases and features with their `cfg!` value as a `&'static [(&'static str, bool)]`.
+/// 
+/// ## Description
+/// cfg_boost_active! expands to a table of every [predefined alias](https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax#predefined-aliases), 
+/// every alias set in configuration or with `cfg_boost-*` environment variables and every feature of Cargo.toml, 
+/// each paired with `cfg!` of its resolved predicate. Useful to report how a binary was compiled, like in `--version --verbose` or crash reports.
+/// A configured alias that isn't an identifier or cannot be resolved is a compilation error.
+/// 
+/// **cfg_boost_active! has no runtime cost.**
+/// 
+/// ## Example
+/// ```ignore
+/// for (name, active) in cfg_boost_active!() {
+///     println!("{} : {}", name, active);
+/// }
+/// ```
+#[proc_macro]
+pub fn cfg_boost_active(_item: TokenStream) -> TokenStream {
+
+    // TokenStream that accumulate content, starting with configuration files tracking.
+    let mut content = generate_tracking_ts();
+
+    // 1. Resolve predicates of predefined aliases, then configured aliases not overriding them. Aliases environment variables are tracked.
+    let mut entries : Vec<(String, String)> = Vec::new();
+    for alias in cfg_boost_parser::ALIASES.iter().map(|(alias, _)| String::from(*alias)).chain(config::get_cfg_boost_aliases()) {
+        if !entries.iter().any(|(name, _)| alias.eq(name)) {
+            content.extend(format!("const _ : Option<&str> = option_env!({});", Literal::string(&format!("{}{}", cfg_boost_parser::ENV_KEY_ALIAS, alias)))
+                .parse::<TokenStream>().unwrap());
+            let predicate = generate_alias_predicate(&alias);
+            entries.push((alias, predicate));
+        }
+    }
+
+    // 2. Add features of Cargo.toml
+    for feature in config::get_cfg_boost_features() {
+        let predicate = format!("feature = {}", Literal::string(&feature));
+        entries.push((feature, predicate));
+    }
+
+    // 3. Generate table
+    let table : Vec<String> = entries.iter().map(|(name, predicate)| format!("({}, cfg!({}))", Literal::string(name), predicate)).collect();
+    content.extend(format!("&[{}]", table.join(", ")).parse::<TokenStream>().unwrap());
+
+    TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace,content)))
+
+}
+
+/// Generate cfg predicate of an alias listed by cfg_boost_active!.
+/// 
+/// Panic(s)
+/// Will panic! with [CfgBoostError::InvalidAlias] if alias isn't an identifier or cannot be resolved.
+fn generate_alias_predicate(alias : &str) -> String {
+    match cfg_boost_parser::tokenize(alias) {
+        Ok(tokens) if matches!(tokens.as_slice(), [Token::Ident(name)] if name.eq(alias)) => match SyntaxTreeNode::generate(&tokens, config::get_config().as_ref()) {
+            Ok(tree) => tree.to_cfg_string(),
+            Err(err) => panic!("{}", CfgBoostError::InvalidAlias(String::from(alias)).message(&err.to_string())),
+        },
+        _ => panic!("{}", CfgBoostError::InvalidAlias(String::from(alias)).message("Aliases names must be identifiers.")),
+    }
+}
+
+/// Procedural macro like [cfg!](https://doc.rust-lang.org/std/macro.cfg.html) with [simplified syntax](https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax).
+/// 
+/// ## Description
+/// is_cfg! evaluates predicates to a `bool` at compile time, exactly like `cfg!`. Aliases and predicates are resolved like [target_cfg!] arms.
+/// 
+/// **is_cfg! has no runtime cost.**
+/// 
+/// ## Syntax
+/// ```ignore
+/// is_cfg!(!? alias* (| &)? !? value:pred*)
+/// ```
+/// [More details on syntax here.](https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax)
+/// 
+/// ## Example
+/// **This**
+/// ```ignore
+/// if cfg!(any(target_os = "linux", target_os = "android")) {
+///     println!("Linux kernel");
+/// }
+/// ```
+/// **becomes**
+/// ```ignore
+/// if is_cfg!(linux | android:os) {
+///     println!("Linux kernel");
+/// }
+/// ```
+#[proc_macro]
+pub fn is_cfg(item: TokenStream) -> TokenStream {
+
+    // 1. Resolve predicates like an arm
+    let predicate = generate_cfg_predicate(item);
+
+    // 2. Track configuration files and generate cfg!
+    let mut content = generate_tracking_ts();
+    content.extend(format!("cfg!({})", predicate).parse::<TokenStream>().unwrap());
+
+    TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace,content)))
+
+}
+
+
+/// Procedural macro like `if cfg!(..) {} else {}` with [simplified syntax](https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax).
+/// 
+/// ## Description
+/// if_cfg! is written like an [if](https://doc.rust-lang.org/std/keyword.if.html) expression with predicates instead of conditions.
+/// Contrary to [match_cfg!], **every branch is type-checked on every platform** and the optimizer removes branches that never match.
+/// Thus branches must only use items available on every platform.
+/// 
+/// ## Syntax
+/// ```ignore
+/// if_cfg!{
+///     !? alias* (| &)? !? value:pred* { .. }
+///     (else if !? alias* (| &)? !? value:pred* { .. })*
+///     (else { .. })?
+/// }
+/// ```
+/// [More details on syntax here.](https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax)
+/// 
+/// ## Example
+/// ```ignore
+/// let separator = if_cfg!{ windows { '\\' } else { '/' } };
+/// 
+/// if_cfg!{
+///     desktop {
+///         println!("desktop");
+///     } else if mobile {
+///         println!("mobile");
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn if_cfg(item: TokenStream) -> TokenStream {
+
+    // TokenStream that accumulate content, starting with configuration files tracking.
+    let mut content = generate_tracking_ts();
+
+    // 1. Split branches
+    let (branches, else_block) = syntax::split_branches(item);
+
+    // 2. Generate `if cfg!(..) {} else if cfg!(..) {}` chain
+    for (index, (predicate, block)) in branches.into_iter().enumerate() {
+        if index > 0 {
+            content.extend("else".parse::<TokenStream>().unwrap());
+        }
+        content.extend(format!("if cfg!({})", generate_cfg_predicate(predicate)).parse::<TokenStream>().unwrap());
+        content.extend(TokenStream::from(TokenTree::from(block)));
+    }
+
+    // 3. Add else block
+    if let Some(block) = else_block {
+        content.extend("else".parse::<TokenStream>().unwrap());
+        content.extend(TokenStream::from(TokenTree::from(block)));
+    }
+
+    // 4. Add braces around content then return it.
+    TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace,content)))
+
+}
+
+/// Generate cfg predicate of simplified syntax predicates, resolved like an arm.
+/// 
+/// Panic(s)
+/// Will panic! if predicates are malformed or cannot be resolved.
+fn generate_cfg_predicate(predicates : TokenStream) -> String {
+    match TargetArm::generate_tree(TargetArmType::Simplified, predicates) {
+        Some(tree) => tree.to_cfg_string(),
+        None => String::from(arm::MODIFIER_ACTIVATE_VALUE),
+    }
+}
+
+
+/// Procedural macro used in build scripts to match the target being compiled for.
+/// 
+/// ## Description
+/// Build scripts are compiled for the host, thus `#[cfg]` and [match_cfg!] describe the host and not the target.
+/// match_target! use the same syntax, arms and rules as [match_cfg!] but each arm predicates are checked at runtime 
+/// against `CARGO_CFG_*` and `CARGO_FEATURE_*` environment variables set by Cargo for build scripts. 
+/// 
+/// The first matching arm is evaluated and all possible values must be covered with a [wildcard](https://doc.rust-lang.org/reference/patterns.html#wildcard-pattern).
+/// Every arm content is compiled and type-checked.
+/// 
+/// ## Syntax
+/// ```ignore
+/// match_target!{
+///     #![dump]?    // Optional options header
+///     ('label:)? !? alias* (| &)? !? value:pred* => {},+
+///     #[cfg(legacy_syntax)] => {},+    // match_target! also support legacy syntax
+///     _ => {}+?     // Mandatory wildcard arm
+/// };?
+/// ```
+/// [More details on syntax here.](https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax)
+/// 
+/// ## Example
+/// **This**
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     let os = std::env::var("CARGO_CFG_TARGET_OS").unwrap();
+///     if os == "linux" || os == "android" {
+///         println!("cargo::rustc-link-lib=dylib=vulkan");
+///     } else if os == "windows" && std::env::var_os("CARGO_FEATURE_GL").is_some() {
+///         println!("cargo::rustc-link-lib=dylib=opengl32");
+///     }
+/// }
+/// ```
+/// **becomes**
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     match_target!{
+///         linux | android:os => println!("cargo::rustc-link-lib=dylib=vulkan"),
+///         windows & gl:ft => println!("cargo::rustc-link-lib=dylib=opengl32"),
+///         _ => {},
+///     };
+/// }
+/// ```
+#[proc_macro]
+pub fn match_target(item: TokenStream) -> TokenStream {
+
+    // TokenStream that accumulate content, starting with configuration files tracking.
+    let mut content = generate_tracking_ts();
+
+    // 1. Extract target arms with match_cfg! rules
+    let arms = TargetArm::extract(item.clone(), CfgBoostMacroSource::MatchMacro, MATCH_TARGET_NAME);
+
+    // 2. Apply release modifier behaviour
+    content.extend(TargetArm::generate_release_modifier_ts(&arms, true));
+
+    // 3. Generate `if .. {} else if .. {} else {}` chain with runtime conditions.
+    let chain = generate_arms_chain(&arms, |predicate| runtime::generate_condition(&syntax::to_tokens(predicate)));
+
+    // 4. Print dumped chain and write report
+    if arms.iter().any(|arm| arm.options.dump) {
+        dump_expansion(MATCH_TARGET_NAME, &[syntax::pretty_print(chain.clone())]);
+    }
+    report::write_report(MATCH_TARGET_NAME, &arms, &[]);
+
+    // 5. Add braces around content then return it.
+    content.extend(chain);
+    TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace,content)))
+
+}
+
+
 /// Attribute macro like [cfg](https://doc.rust-lang.org/rust-by-example/attribute/cfg.html) with [simplified syntax](https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax) used for one item.
 /// 
 /// ## Description
@@ -298,6 +790,9 @@ pub fn match_cfg(item: TokenStream) -> TokenStream {
 /// 
 /// #[meta_cfg(#[cfg(legacy_syntax)])]  // meta_cfg also support legacy syntax.
 /// item
+/// 
+/// #[meta_cfg(#![autodoc(false)] !? alias* (| &)? !? value:pred*)]  // Options header like target_cfg!
+/// item
 /// ```
 /// [More details on syntax here.](https://github.com/NickelAngeStudio/cfg_boost/wiki/Syntax)
 /// 
@@ -330,6 +825,6 @@ pub fn meta_cfg(attr: TokenStream, item: TokenStream) -> TokenStream {
     stream.extend(TokenStream::from(TokenTree::from(Group::new(Delimiter::Brace,item))));   // Add braced content
 
     // 2. Generate tokenstream with target_cfg! macro
-    target_cfg(stream)
+    generate_target_cfg(stream, META_CFG_NAME)
 
 }
\ No newline at end of file
@@ -0,0 +1,134 @@
+// Options of a macro invocation, set with an inner attribute header.
+
+use proc_macro::{TokenStream, TokenTree, Delimiter};
+
+use crate::{errors::CfgBoostError, config::{is_cfg_boost_autodoc, is_cfg_boost_doc_stub, is_cfg_boost_availability_notes, get_docsrs_cfg, get_docsrs_cfg_name, is_cfg_boost_dump},
+    TARGET_CFG_NAME, META_CFG_NAME, MATCH_CFG_NAME, MATCH_TARGET_NAME, CFG_LABEL_NAME, CFG_PREDICATE_NAME};
+
+/// Options header symbols `#![]`
+const OPTIONS_HEADER_0 : char = '#';
+const OPTIONS_HEADER_1 : char = '!';
+
+/// Options separator
+const OPTIONS_SEPARATOR : char = ',';
+
+/// Options keys
+const OPTION_AUTODOC : &str = "autodoc";
+const OPTION_DOCSRS_TAG : &str = "docsrs_tag";
+const OPTION_DOC_STUB : &str = "doc_stub";
+const OPTION_AVAILABILITY_NOTES : &str = "availability_notes";
+const OPTION_DUMP : &str = "dump";
+const OPTION_CHECKED : &str = "checked";
+
+/// Macros accepting documentation options
+const DOC_MACROS : &[&str] = &[TARGET_CFG_NAME, META_CFG_NAME];
+
+/// Macros accepting dump option
+const DUMP_MACROS : &[&str] = &[TARGET_CFG_NAME, META_CFG_NAME, MATCH_CFG_NAME, MATCH_TARGET_NAME, CFG_LABEL_NAME, CFG_PREDICATE_NAME];
+
+/// Options keys and macros accepting them
+const OPTIONS : [(&str, &[&str]); 6] = [(OPTION_AUTODOC, DOC_MACROS), (OPTION_DOCSRS_TAG, DOC_MACROS), (OPTION_DOC_STUB, DOC_MACROS),
+    (OPTION_AVAILABILITY_NOTES, DOC_MACROS), (OPTION_DUMP, DUMP_MACROS), (OPTION_CHECKED, &[MATCH_CFG_NAME])];
+
+/// Options values
+const OPTION_TRUE : &str = "true";
+const OPTION_FALSE : &str = "false";
+
+/// Options of a macro invocation.
+///
+/// Default values come from configuration and are overridden by header like `#![autodoc(false), docsrs_tag(false)]`.
+/// An option without value like `#![doc_stub]` is set to true.
+#[derive(Clone)]
+pub(crate) struct MacroOptions {
+    pub autodoc : bool,                 // Add doc to predicates
+    pub docsrs_cfg : Option<String>,    // cfg name of docs.rs tags, None if not generated
+    pub doc_stub : bool,                // Document non-matching arms with stub copies
+    pub availability_notes : bool,      // Add availability notes to documentation
+    pub dump : bool,                    // Print generated tokens to stderr
+    pub checked : bool,                 // Lower match_cfg! to an `if cfg!()` chain type-checking every arm
+}
+
+impl MacroOptions {
+    /// Create options from configuration.
+    pub fn new() -> MacroOptions {
+        MacroOptions { autodoc : is_cfg_boost_autodoc(), docsrs_cfg : get_docsrs_cfg(), doc_stub : is_cfg_boost_doc_stub(), availability_notes : is_cfg_boost_availability_notes(), dump : is_cfg_boost_dump(), checked : false }
+    }
+
+    /// Extract options header at the start of macro_name source, if any.
+    ///
+    /// Returns options and source without header.
+    ///
+    /// Panic(s)
+    /// Will panic! if an option is unknown, doesn't apply to macro_name or its value isn't a boolean.
+    pub fn extract(source : TokenStream, macro_name : &str) -> (MacroOptions, TokenStream) {
+
+        let mut options = MacroOptions::new();
+        let tokens : Vec<TokenTree> = source.clone().into_iter().collect();
+
+        // 1. Header is `#` followed by `!` and []. Legacy arms `#[]` don't have `!`.
+        match (tokens.first(), tokens.get(1), tokens.get(2)) {
+            (Some(TokenTree::Punct(p0)), Some(TokenTree::Punct(p1)), Some(TokenTree::Group(grp)))
+                if p0.as_char() == OPTIONS_HEADER_0 && p1.as_char() == OPTIONS_HEADER_1 && grp.delimiter() == Delimiter::Bracket => {
+
+                // 2. Set each option of header
+                let mut key : Option<String> = None;
+                for token in grp.stream() {
+                    match token {
+                        TokenTree::Ident(ident) if key.is_none() => key = Some(ident.to_string()),
+                        TokenTree::Group(value) if key.is_some() && value.delimiter() == Delimiter::Parenthesis => {
+                            options.set(key.take().unwrap().as_str(), value.stream().to_string().as_str(), macro_name);
+                        },
+                        TokenTree::Punct(punct) if punct.as_char() == OPTIONS_SEPARATOR => if let Some(key) = key.take() {
+                            options.set(key.as_str(), OPTION_TRUE, macro_name);     // Option without value
+                        },
+                        _ => panic!("{}", CfgBoostError::InvalidOption(token.to_string()).message(macro_name)),
+                    }
+                }
+                if let Some(key) = key {
+                    options.set(key.as_str(), OPTION_TRUE, macro_name);
+                }
+
+                // 3. Return options and source without header
+                (options, tokens[3..].iter().cloned().collect())
+            },
+            _ => (options, source),
+        }
+
+    }
+
+    /// Set option value from header of macro_name.
+    fn set(&mut self, key : &str, value : &str, macro_name : &str) {
+
+        // 1. Option must be accepted by macro
+        if !macro_options(macro_name).contains(&key) {
+            panic!("{}", CfgBoostError::InvalidOption(String::from(key)).message(macro_name));
+        }
+
+        // 2. Set boolean value
+        let value = match value {
+            OPTION_TRUE => true,
+            OPTION_FALSE => false,
+            _ => panic!("{}", CfgBoostError::InvalidOptionValue(String::from(key)).message(value)),
+        };
+
+        match key {
+            OPTION_AUTODOC => self.autodoc = value,
+            OPTION_DOCSRS_TAG => self.docsrs_cfg = if value {
+                    self.docsrs_cfg.take().or(Some(get_docsrs_cfg_name()))
+                } else {
+                    None
+                },
+            OPTION_DOC_STUB => self.doc_stub = value,
+            OPTION_AVAILABILITY_NOTES => self.availability_notes = value,
+            OPTION_DUMP => self.dump = value,
+            OPTION_CHECKED => self.checked = value,
+            _ => panic!("{}", CfgBoostError::InvalidOption(String::from(key)).message(macro_name)),
+        }
+
+    }
+}
+
+/// Returns options accepted in header of macro_name.
+pub(crate) fn macro_options(macro_name : &str) -> Vec<&'static str> {
+    OPTIONS.iter().filter(|(_, macros)| macros.contains(&macro_name)).map(|(option, _)| *option).collect()
+}
@@ -0,0 +1,90 @@
+// JSON reports of macro expansions written to `CFG_BOOST_REPORT_DIR` directory.
+
+use std::{env, fs, sync::atomic::{AtomicUsize, Ordering}};
+
+use proc_macro::TokenStream;
+
+use cfg_boost_parser::json_string;
+
+use crate::{arm::TargetArm, config::get_cfg_boost_report_dir, errors::CfgBoostError, syntax::{item_keyword, item_name}};
+
+/// Crate name key set by Cargo
+const CARGO_CRATE_NAME : &str = "CARGO_CRATE_NAME";
+
+/// Report file extension
+const REPORT_EXTENSION : &str = "json";
+
+/// Reports written by this compilation, naming reports of invocations without location.
+static REPORT_COUNT : AtomicUsize = AtomicUsize::new(0);
+
+/// Write JSON report of a macro expansion in report directory, if `CFG_BOOST_REPORT_DIR` is set.
+///
+/// Report file is named from crate, file and position of invocation so each invocation has its own report.
+/// Without span locations (before Rust 1.88), location is null and report is named from crate, macro and order of invocation.
+/// `arms_items` are the items generated by each arm, empty for match_cfg!.
+///
+/// Panic(s)
+/// Will panic! if report cannot be written.
+pub(crate) fn write_report(macro_name : &str, arms : &[TargetArm], arms_items : &[Vec<TokenStream>]) {
+
+    if let Some(dir) = get_cfg_boost_report_dir() {
+
+        // 1. Invocation location
+        let crate_name = env::var(CARGO_CRATE_NAME).unwrap_or_default();
+        let location = invocation_location();
+
+        // 2. Arms records
+        let arms_json : Vec<String> = arms.iter().enumerate().map(|(index, arm)| {
+            let items : Vec<String> = arms_items.get(index).map(|items| items.iter().map(|item| format!("{{\"keyword\":{},\"name\":{}}}",
+                json_option(item_keyword(item)),
+                json_option(item_name(item).and_then(|name| name.split_once(' ').map(|(_, name)| String::from(name)))))).collect()).unwrap_or_default();
+
+            format!("{{\"source\":{},\"label\":{},\"type\":\"{:?}\",\"modifier\":\"{:?}\",\"predicate\":{},\"cfg\":{},\"tree\":{},\"items\":[{}]}}",
+                json_string(&arm.arm_ts.to_string()), json_option(arm.label.clone()), arm.arm_type, arm.modifier, json_string(&arm.pred_ts.to_string()),
+                json_string(&arm.cfg_ts.to_string()), arm.tree.as_ref().map(|tree| tree.to_json()).unwrap_or(String::from("null")), items.join(","))
+        }).collect();
+
+        // 3. Expansion record
+        let (file, line, column) = match &location {
+            Some((file, line, column)) => (json_string(file), line.to_string(), column.to_string()),
+            None => (String::from("null"), String::from("null"), String::from("null")),
+        };
+        let report = format!("{{\"crate\":{},\"file\":{},\"line\":{},\"column\":{},\"macro\":{},\"arms\":[{}]}}\n",
+            json_string(&crate_name), file, line, column, json_string(macro_name), arms_json.join(","));
+
+        // 4. Write report file
+        let file_name = match location {
+            Some((file, line, column)) => format!("{}-{}-{}-{}", crate_name, file, line, column),
+            None => format!("{}-{}-{}", crate_name, macro_name, REPORT_COUNT.fetch_add(1, Ordering::Relaxed)),
+        };
+        let file_name : String = file_name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect();
+        let path = dir.join(format!("{}.{}", file_name, REPORT_EXTENSION));
+
+        if fs::create_dir_all(&dir).and_then(|_| fs::write(&path, report)).is_err() {
+            panic!("{}", CfgBoostError::ReportNotWritten(path.display().to_string()).message(""));
+        }
+    }
+
+}
+
+/// Get file, line and column of macro invocation.
+#[cfg(span_locations)]
+fn invocation_location() -> Option<(String, usize, usize)> {
+    let span = proc_macro::Span::call_site();
+    Some((span.file(), span.line(), span.column()))
+}
+
+/// Span locations need Rust 1.88, invocation isn't located.
+#[cfg(not(span_locations))]
+fn invocation_location() -> Option<(String, usize, usize)> {
+    None
+}
+
+/// Write optional string as JSON string or null.
+fn json_option(value : Option<String>) -> String {
+    match value {
+        Some(value) => json_string(&value),
+        None => String::from("null"),
+    }
+}
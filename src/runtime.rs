@@ -0,0 +1,54 @@
+// Runtime checks of cfg predicates against `CARGO_CFG_*` and `CARGO_FEATURE_*` environment variables, for build scripts.
+
+use cfg_boost_parser::{Token, Delimiter, to_source, CFG_ANY, CFG_ALL, CFG_NOT, CFG_TRUE, CFG_FALSE};
+
+use crate::errors::CfgBoostError;
+
+/// Environment variables prefixes set by Cargo for build scripts
+const CARGO_CFG_PREFIX : &str = "CARGO_CFG_";
+const CARGO_FEATURE_PREFIX : &str = "CARGO_FEATURE_";
+
+/// Cfg feature key
+const CFG_FEATURE : &str = "feature";
+
+/// Separator of values in `CARGO_CFG_*` environment variables, like `CARGO_CFG_TARGET_FAMILY=unix,wasm`.
+const CARGO_CFG_VALUES_SEPARATOR : char = ',';
+
+/// Generate runtime condition of cfg predicate tokens like `any(unix, target_os = "windows")`.
+///
+/// Cfg options are read from `CARGO_CFG_*` environment variables and features from `CARGO_FEATURE_*`, which Cargo sets for build scripts.
+///
+/// Panic(s)
+/// Will panic! if predicate cannot be checked at runtime.
+pub(crate) fn generate_condition(predicate : &[Token]) -> String {
+
+    match predicate {
+        // any(..), all(..) and not(..)
+        [Token::Ident(ident), Token::Group(Delimiter::Parenthesis, group)] => {
+            let operands : Vec<String> = group.split(|t| matches!(t, Token::Punct(','))).filter(|operand| !operand.is_empty()).map(generate_condition).collect();
+            match (ident.as_str(), operands.len()) {
+                (CFG_ANY, 0) => String::from(CFG_FALSE),
+                (CFG_ALL, 0) => String::from(CFG_TRUE),
+                (CFG_ANY, _) => format!("({})", operands.join(" || ")),
+                (CFG_ALL, _) => format!("({})", operands.join(" && ")),
+                (CFG_NOT, 1) => format!("!{}", operands[0]),
+                _ => panic!("{}", CfgBoostError::RuntimePredicate.message(&to_source(predicate))),
+            }
+        },
+        // feature = "value"
+        [Token::Ident(key), Token::Punct('='), Token::Literal(value)] if key.eq(CFG_FEATURE) =>
+            format!("::std::env::var_os(\"{}{}\").is_some()", CARGO_FEATURE_PREFIX, value.trim_matches('"').to_uppercase().replace('-', "_")),
+        // key = "value"
+        [Token::Ident(key), Token::Punct('='), Token::Literal(value)] =>
+            format!("::std::env::var(\"{}{}\").unwrap_or_default().split('{}').any(|value| value == {})", CARGO_CFG_PREFIX, key.to_uppercase(), CARGO_CFG_VALUES_SEPARATOR, value),
+        // key, true or false
+        [Token::Ident(key)] => match key.as_str() {
+            CFG_TRUE | CFG_FALSE => key.clone(),
+            key => format!("::std::env::var_os(\"{}{}\").is_some()", CARGO_CFG_PREFIX, key.to_uppercase()),
+        },
+        // Invisible group of macro_rules!
+        [Token::Group(Delimiter::None, group)] => generate_condition(group),
+        _ => panic!("{}", CfgBoostError::RuntimePredicate.message(&to_source(predicate))),
+    }
+
+}
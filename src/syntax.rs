@@ -1,293 +1,476 @@
-// Syntax tree used to generate configuration from TokenStream.
+// Tokenstream helpers of macros items and conversion into parser tokens.
 
-use std::{rc::Rc};
-use proc_macro::{TokenStream, TokenTree};
+use proc_macro::{TokenStream, TokenTree, Delimiter, Group};
 
-use crate::{errors::CfgBoostError, config::{get_cfg_boost_alias, get_cfg_boost_predicate}};
+use cfg_boost_parser::{self as parser, Token};
 
-/// SyntaxTreeNode in a RC 
-pub(crate) type Node = Rc<SyntaxTreeNode>;
+use crate::errors::CfgBoostError;
 
-/// Negative symbol
-pub(crate) const NEGATIVE_SYMBOL : char = '!';
+/// Convert tokenstream into parser tokens.
+#[inline(always)]
+pub(crate) fn to_tokens(stream : TokenStream) -> Vec<Token> {
+    stream.into_iter().map(|t| match t {
+        TokenTree::Ident(ident) => Token::Ident(ident.to_string()),
+        TokenTree::Punct(punc) => Token::Punct(punc.as_char()),
+        TokenTree::Literal(literal) => Token::Literal(literal.to_string()),
+        TokenTree::Group(grp) => Token::Group(match grp.delimiter() {
+            Delimiter::Parenthesis => parser::Delimiter::Parenthesis,
+            Delimiter::Brace => parser::Delimiter::Brace,
+            Delimiter::Bracket => parser::Delimiter::Bracket,
+            Delimiter::None => parser::Delimiter::None,
+        }, to_tokens(grp.stream())),
+    }).collect()
+}
 
-/// Symbol for AND.
-pub(crate) const AND_SYMBOL : char = '&';
+/// Write tokenstream as written in source, like `linux & !aarch64:ar`.
+///
+/// Spaces between tokens come from their spans. Tokens without source text, like generated tokens, are written with [parser::to_source].
+#[cfg(span_locations)]
+pub(crate) fn source_text(stream : TokenStream) -> String {
+
+    let tokens : Vec<TokenTree> = stream.into_iter().collect();
+    let mut text = String::new();
+
+    for (index, token) in tokens.iter().enumerate() {
+        match token.span().source_text() {
+            Some(source) => {
+                // Spaces of same line or a single space between lines.
+                if let Some(previous) = index.checked_sub(1).map(|index| &tokens[index]) {
+                    let (end, start) = (previous.span().end(), token.span().start());
+                    let spaces = if end.line() == start.line() { start.column().saturating_sub(end.column()) } else { 1 };
+                    text.push_str(&" ".repeat(spaces));
+                }
+                text.push_str(&source);
+            },
+            None => return parser::to_source(&to_tokens(tokens.into_iter().collect())),
+        }
+    }
 
-/// Symbol for OR.
-pub(crate) const OR_SYMBOL : char = '|';
+    text
 
+}
 
-/// Syntax tree node used to parse attribute tokens.
-#[derive(Debug)]
-pub(crate) enum SyntaxTreeNode {
-    /// A Not node
-    NOT(Node),
+/// Write tokenstream with [parser::to_source], since span locations need Rust 1.88.
+#[cfg(not(span_locations))]
+pub(crate) fn source_text(stream : TokenStream) -> String {
+    parser::to_source(&to_tokens(stream))
+}
 
-    /// A or(|) operation
-    ANY(Node, Node),
+/// Split tokenstream in different [item](https://doc.rust-lang.org/reference/items.html) vector tokenstream.
+/// 
+/// An item is defined as all tokens until a ; and/or {}.
+#[inline(always)]
+pub(crate) fn split_items(stream : TokenStream) -> Vec<TokenStream> {
 
-    /// A and(&) operation
-    ALL(Node, Node),
+    let mut item = TokenStream::new();
+    let mut items : Vec<TokenStream> = Vec::new();
+
+    for t in stream {
+        match &t {
+            proc_macro::TokenTree::Group(grp) => {
+                // Validate if first and last character of group is 
+                match grp.delimiter(){
+                    proc_macro::Delimiter::Brace => {    // End of item. 
+                        item.extend(TokenStream::from(t)); // Add into item tokenstream
+                        items.push(item);   // Push item into vector.
+                        item = TokenStream::new();  // Reset item tokenstream
+                    },
+                    _ => item.extend(TokenStream::from(t)), // Add into item tokenstream
+                }
+            }
+            ,
+            proc_macro::TokenTree::Punct(punc) => {
+                if punc.as_char().eq(&';') { // End of item.
+                    item.extend(TokenStream::from(t)); // Add into item tokenstream
+                    items.push(item);   // Push item into vector.
+                    item = TokenStream::new();  // Reset item tokenstream
+                } else {
+                    item.extend(TokenStream::from(t)); // Add into item tokenstream
+                }
+            },
+            _ => item.extend(TokenStream::from(t)), // Add into item tokenstream
+        }
+    }
 
-    /// End leaf of the tree
-    LEAF(String)
+    items
 }
 
-impl ToString for SyntaxTreeNode {
-    /// Write the node as string. The format will be the same as used with #[cfg()].
-    fn to_string(&self) -> String {
-        match self {
-            SyntaxTreeNode::NOT(node) => format!("not({})", node.to_string()),
-            SyntaxTreeNode::ANY(left_node, right_node) => format!("any({},{})", left_node.to_string(), right_node.to_string()),
-            SyntaxTreeNode::ALL(left_node, right_node) => format!("all({},{})", left_node.to_string(), right_node.to_string()),
-            SyntaxTreeNode::LEAF(label) => 
-                match get_cfg_boost_predicate(&label.as_str()) {
-                    Ok(predicate) => format!("{}", predicate),
-                    Err(err) => panic!("{}", err.message(label)),
-                },
+/// Insert tokenstream in item after its outer attributes, like after its documentation.
+#[inline(always)]
+pub(crate) fn insert_after_attributes(item : TokenStream, inserted : TokenStream) -> TokenStream {
+
+    let tokens : Vec<TokenTree> = item.into_iter().collect();
+
+    // 1. Find end of outer attributes, made of `#` followed by [].
+    let mut position = 0;
+    while let (Some(TokenTree::Punct(punc)), Some(TokenTree::Group(grp))) = (tokens.get(position), tokens.get(position + 1)) {
+        if punc.as_char().eq(&'#') && grp.delimiter() == Delimiter::Bracket {
+            position += 2;
+        } else {
+            break;
         }
     }
+
+    // 2. Insert tokens after attributes
+    let mut stream : TokenStream = tokens[..position].iter().cloned().collect();
+    stream.extend(inserted);
+    stream.extend(tokens[position..].iter().cloned());
+    stream
+
 }
 
-impl SyntaxTreeNode {
-    /// Create a NOT SyntaxTreeNode
-    pub fn not_node(child : Node) -> Node {
-        Rc::new(SyntaxTreeNode::NOT(child.clone()))
-    }
+/// Keywords of items that can only be declared in a module or a block and never in an `impl` or `trait`.
+pub(crate) const MODULE_ITEM_KEYWORDS : [&str; 9] = ["struct", "enum", "union", "mod", "use", "trait", "impl", "static", "macro_rules"];
 
-    /// Create an ALL SyntaxTreeNode
-    pub fn all_node(left : Node, right : Node) -> Node {
-        Rc::new(SyntaxTreeNode::ALL(left.clone(), right.clone()))
-    }
+/// Keywords that can precede the item keyword.
+const ITEM_QUALIFIERS : [&str; 4] = ["pub", "unsafe", "async", "default"];
 
-    /// Create an ANY SyntaxTreeNode
-    pub fn any_node(left : Node, right : Node) -> Node {
-        Rc::new(SyntaxTreeNode::ANY(left.clone(), right.clone()))
-    }
+/// Get the keyword of an item (`fn`, `struct`, `impl`, ...), skipping attributes, visibility and qualifiers.
+#[inline(always)]
+pub(crate) fn item_keyword(item : &TokenStream) -> Option<String> {
 
-    /// Generate a SyntaxTreeNode from token stream.
-    pub(crate) fn generate(stream : TokenStream) -> Node {
-
-        match split_tokenstream_at_operator(stream.clone()){
-            // Means we have to split 
-            Some((operator, left, right)) => 
-                match operator {
-                    AND_SYMBOL => {    // ALL node
-                        return Self::all_node(Self::generate(left), Self::generate(right));
-                    }
-                    OR_SYMBOL => {    // ANY node
-                        return Self::any_node(Self::generate(left), Self::generate(right));
-                    },
-                    _ =>  panic!("{}", CfgBoostError::InvalidCharacter(String::from(operator)).message(&stream.to_string())),
-                },
-            // No split. Must evaluate if not node, etc...
-            None => {
-                // Is NOT node?
-                let (symbol, content) = extract_negative_symbol(stream.clone());
-                
-                if is_not_node(symbol) {
-                    // Create a NOT node
-                    return Self::not_node(Self::generate(content));
-                } else {
+    // Flag used to skip group following `#` or `pub`
+    let mut skip_group = false;
 
-                    // Extract group
-                    match extract_group(content.clone()) {
-                        Some(group) => return Self::generate(group),
-                        None => {
-                            // Verify that node isn't empty.
-                            if content.to_string().eq("") { // Make sure node isn't empty
-                                panic!("{}", CfgBoostError::EmptyNode.message(&content.to_string()));
-                            }
-
-                            match content.to_string().find(":"){
-                                Some(pos) => {    // End LEAF reached
-                                    match content.to_string()[..pos].trim().find(" "){    // Make sure node doesn't contains spaces.
-                                        Some(_) => panic!("{}", CfgBoostError::MissingOperator.message(&content.to_string())),
-                                        None => {},
-                                    }
-
-                                    return Rc::new(SyntaxTreeNode::LEAF(content.to_string()));
-                                },
-                                None => {   // Unwrap alias
-                                    match content.to_string().find(" "){    // Make sure node doesn't contains spaces.
-                                        Some(_) => panic!("{}", CfgBoostError::MissingOperator.message(&content.to_string())),
-                                        None => {},
-                                    }
-                                    match get_cfg_boost_alias(&content.to_string()) {
-                                        Ok(alias) => Self::generate(alias.parse().unwrap()),
-                                        Err(err) => panic!("{}", err.message(&stream.to_string())),
-                                    }
-                                },
-                            }
-                        },
-                    }
+    for t in item.clone() {
+        match t {
+            TokenTree::Punct(punc) => skip_group = punc.as_char().eq(&'#') || punc.as_char().eq(&'!'),
+            TokenTree::Group(_) => if !skip_group {
+                return None;
+            },
+            TokenTree::Ident(ident) => {
+                let ident = ident.to_string();
+                if ITEM_QUALIFIERS.contains(&ident.as_str()) {
+                    skip_group = true;
+                } else {
+                    return Some(ident);
                 }
             },
+            TokenTree::Literal(_) => return None,
         }
-
     }
 
+    None
 }
 
-/// Extract a group from token stream
+/// Keywords of items declaring a name, with their namespace.
+const NAMED_ITEM_KEYWORDS : [(&str, &str); 10] = [("fn", "value"), ("const", "value"), ("static", "value"), ("struct", "type"), ("enum", "type"), 
+    ("union", "type"), ("mod", "type"), ("trait", "type"), ("type", "type"), ("macro_rules", "macro")];
+
+/// Get the name of an item prefixed by its namespace (`value new`, `type Foo`, ...).
+/// 
+/// Return None for items without name like `impl` and `use`.
 #[inline(always)]
-fn extract_group(stream : TokenStream) -> Option<TokenStream> {
-    for t in stream {
-        match t {
-            TokenTree::Group(group) => return Some(group.stream()),
-            _ => {},
+pub(crate) fn item_name(item : &TokenStream) -> Option<String> {
+
+    // Namespace of the last item keyword found
+    let mut namespace : Option<&str> = None;
+
+    for t in item.clone() {
+        match (t, namespace) {
+            (TokenTree::Ident(ident), _) => {
+                let ident = ident.to_string();
+                match (NAMED_ITEM_KEYWORDS.iter().find(|(keyword, _)| ident.eq(keyword)), namespace) {
+                    (Some((_, ns)), _) => namespace = Some(ns),     // `const fn` will be in `fn` namespace
+                    (None, Some(_)) if ident.eq("mut") => {},       // `static mut`
+                    (None, Some(_)) if ident.eq("_") => return None,    // Unnamed `const _`
+                    (None, Some(ns)) => return Some(format!("{} {}", ns, ident)),
+                    (None, None) => {},     // Visibility and qualifiers
+                }
+            },
+            (TokenTree::Punct(punc), Some(_)) if punc.as_char().eq(&'!') => {},  // `macro_rules!`
+            (_, Some(_)) => return None,
+            _ => {},    // Attributes and visibility
         }
     }
+
     None
 }
 
-/// Extract ! at the beginning of node.
+/// Returns true if any item can only be declared in a module or a block.
 /// 
-/// Returns a pair containing ! tokenstream and the rest of the stream without !.
+/// Used to know if generated module items can be added next to items.
 #[inline(always)]
-pub(crate) fn extract_negative_symbol(stream: TokenStream) -> (TokenStream, TokenStream) {
-
-    // Symbol Tokenstream
-    let mut symbol = TokenStream::new();
-
-    // Content Tokenstream
-    let mut content = TokenStream::new();
-
-    // Flag that show if in symbol right now.
-    let mut is_symbol = true;
-
-
-    for t in stream.clone() {
-        if is_symbol {
-            match t.clone() {
-                proc_macro::TokenTree::Punct(punc) => {
-                    match punc.as_char() {
-                        NEGATIVE_SYMBOL => symbol.extend(TokenStream::from(t)),
-                        _ => panic!("{}", CfgBoostError::InvalidCharacter(String::from(punc.as_char())).message(&stream.to_string())),
-                    }
-                },
-                _ => {
-                    is_symbol = false;
-                    content.extend(TokenStream::from(t));
-                },
-            }
-        } else {
-            content.extend(TokenStream::from(t));
+pub(crate) fn has_module_item(items : &[TokenStream]) -> bool {
+    items.iter().any(|item| match item_keyword(item) {
+        Some(keyword) => MODULE_ITEM_KEYWORDS.contains(&keyword.as_str()),
+        None => false,
+    })
+}
+
+/// Insert items at the start of the body of a function or of the initializer of a constant or static, which are blocks accepting
+/// module items even inside an `impl` or `trait`. Returns None if item has no body, like a `type` or a required trait function.
+pub(crate) fn insert_in_body(item : TokenStream, ts : TokenStream) -> Option<TokenStream> {
+
+    let mut tokens : Vec<TokenTree> = item.clone().into_iter().collect();
+
+    // 1. Constants and statics get their initializer between `=` and `;` wrapped in a block.
+    if matches!(tokens.last(), Some(TokenTree::Punct(punc)) if punc.as_char().eq(&';')) {
+        let start = tokens.iter().position(|t| matches!(t, TokenTree::Punct(punc) if punc.as_char().eq(&'=')))? + 1;
+        let end = tokens.len() - 1;
+        match item_keyword(&item).as_deref() {
+            Some(STUB_CONST_KEYWORD | STUB_STATIC_KEYWORD) if start < end => {
+                let mut stream = ts;
+                stream.extend(tokens.drain(start..end));
+                tokens.insert(start, TokenTree::Group(Group::new(Delimiter::Brace, stream)));
+                return Some(tokens.into_iter().collect());
+            },
+            _ => return None,
         }
     }
 
-    (symbol, content)
+    // 2. Functions get items before statements of their body, keeping its span for diagnostics.
+    match tokens.last() {
+        Some(TokenTree::Group(body)) if body.delimiter() == Delimiter::Brace && is_function(&tokens) => {
+            let mut stream = ts;
+            stream.extend(body.stream());
+            let mut block = Group::new(Delimiter::Brace, stream);
+            block.set_span(body.span());
+            *tokens.last_mut().unwrap() = TokenTree::Group(block);
+            Some(tokens.into_iter().collect())
+        },
+        _ => None,
+    }
 
 }
 
-/// Verify if node is a NOT node, or not
+/// Keywords of items whose inner items are stubbed in doc-only copies.
+const STUB_CONTAINER_KEYWORDS : [&str; 3] = ["impl", "trait", "mod"];
+
+/// Keywords of modules and imports, which may refer to target-only paths.
+const STUB_MOD_KEYWORD : &str = "mod";
+const STUB_USE_KEYWORD : &str = "use";
+const STUB_EXTERN_KEYWORD : &str = "extern";
+const STUB_CRATE_KEYWORD : &str = "crate";
+
+/// Keywords of items with an initializer.
+const STUB_CONST_KEYWORD : &str = "const";
+const STUB_STATIC_KEYWORD : &str = "static";
+
+/// Identifiers allowed in literal initializers of free constants.
+const STUB_LITERAL_IDENTS : [&str; 2] = ["true", "false"];
+
+/// Body of functions and initializer of statics and associated constants in doc-only copies. `loop {}` coerce to any type, even in const fn.
+const STUB_FN_BODY : &str = "loop {}";
+
+/// Generate doc-only stub copy of an item. Returns None if item is left out of the copy.
+/// 
+/// Functions bodies are replaced with `loop {}` and items of `impl`, `trait` and `mod` are stubbed recursively. Out-of-line `mod x;` becomes an empty
+/// inline module and `use` and `extern crate` are left out. Initializers of statics and associated constants are replaced with `loop {}`. Free constants
+/// are evaluated even by rustdoc, so only those with a literal initializer are kept. Signatures and other items are copied as is.
+/// 
+/// associated is true if item is inside an `impl` or a `trait`.
+pub(crate) fn stub_item(item : TokenStream, associated : bool) -> Option<TokenStream> {
+
+    let mut tokens : Vec<TokenTree> = item.clone().into_iter().collect();
+    let keyword = item_keyword(&item);
+
+    // 1. Imports are left out.
+    if keyword.as_deref() == Some(STUB_USE_KEYWORD) || is_extern_crate(&tokens) {
+        return None;
+    }
+
+    // 2. Only items ending with a body are stubbed, except out-of-line modules and initializers.
+    let body = match (keyword.as_deref(), tokens.last()) {
+        (_, Some(TokenTree::Group(grp))) if grp.delimiter() == Delimiter::Brace => grp.clone(),
+        (Some(STUB_MOD_KEYWORD), Some(TokenTree::Punct(punc))) if punc.as_char().eq(&';') => {
+            *tokens.last_mut().unwrap() = TokenTree::Group(Group::new(Delimiter::Brace, TokenStream::new()));
+            return Some(tokens.into_iter().collect());
+        },
+        (Some(STUB_CONST_KEYWORD | STUB_STATIC_KEYWORD), _) => return stub_initializer(tokens, associated || keyword.as_deref() == Some(STUB_STATIC_KEYWORD)),
+        _ => return Some(item),
+    };
+
+    // 3. Stub inner items of containers or function body. Items of `impl` and `trait` are associated.
+    let stream = match keyword.as_deref() {
+        Some(keyword) if STUB_CONTAINER_KEYWORDS.contains(&keyword) => split_items(body.stream()).into_iter()
+            .filter_map(|item| stub_item(item, keyword.ne(STUB_MOD_KEYWORD))).collect(),
+        _ if is_function(&tokens) => STUB_FN_BODY.parse::<TokenStream>().unwrap(),
+        _ => return Some(item),
+    };
+
+    // 4. Replace body, keeping its span for diagnostics.
+    let mut stub = Group::new(Delimiter::Brace, stream);
+    stub.set_span(body.span());
+    *tokens.last_mut().unwrap() = TokenTree::Group(stub);
+
+    Some(tokens.into_iter().collect())
+
+}
+
+/// Replace initializer of a const or static item tokens with `loop {}` if replaceable, else keep constants with a literal initializer.
+fn stub_initializer(mut tokens : Vec<TokenTree>, replaceable : bool) -> Option<TokenStream> {
+
+    // 1. Initializer is between `=` and `;`. Items without initializer are kept.
+    let start = tokens.iter().position(|t| matches!(t, TokenTree::Punct(punc) if punc.as_char().eq(&'=')));
+    let end = tokens.iter().rposition(|t| matches!(t, TokenTree::Punct(punc) if punc.as_char().eq(&';')));
+    let (start, end) = match (start, end) {
+        (Some(start), Some(end)) if start < end => (start + 1, end),
+        _ => return Some(tokens.into_iter().collect()),
+    };
+
+    // 2. Replace or keep literal initializer
+    if replaceable {
+        tokens.splice(start..end, STUB_FN_BODY.parse::<TokenStream>().unwrap());
+        Some(tokens.into_iter().collect())
+    } else if is_literal(tokens[start..end].iter().cloned().collect()) {
+        Some(tokens.into_iter().collect())
+    } else {
+        None
+    }
+
+}
+
+/// Returns true if tokens are only literals, punctuations and `true` or `false`, like `-1`, `"text"` or `[1, 2]`.
+fn is_literal(stream : TokenStream) -> bool {
+    stream.into_iter().all(|t| match t {
+        TokenTree::Literal(_) | TokenTree::Punct(_) => true,
+        TokenTree::Ident(ident) => STUB_LITERAL_IDENTS.contains(&ident.to_string().as_str()),
+        TokenTree::Group(grp) => is_literal(grp.stream()),
+    })
+}
+
+/// Returns true if item tokens declare an `extern crate`.
 #[inline(always)]
-fn is_not_node(symbol : TokenStream) -> bool{
+fn is_extern_crate(tokens : &[TokenTree]) -> bool {
+    tokens.windows(2).any(|pair| match pair {
+        [TokenTree::Ident(first), TokenTree::Ident(second)] => first.to_string().eq(STUB_EXTERN_KEYWORD) && second.to_string().eq(STUB_CRATE_KEYWORD),
+        _ => false,
+    })
+}
+
+/// Qualifiers that can precede `fn`, with the ABI literal of `extern "C"`.
+const FN_QUALIFIERS : [&str; 3] = ["const", "extern", "safe"];
+
+/// Returns true if item tokens declare a function, with `fn` after attributes, visibility and qualifiers.
+/// 
+/// Items like `struct S { f : fn() }` contain `fn` but are not functions.
+#[inline(always)]
+fn is_function(tokens : &[TokenTree]) -> bool {
 
-    for t in symbol {
+    // Flag used to skip group following `#`, `!` or `pub`
+    let mut skip_group = false;
+
+    for t in tokens {
         match t {
-            proc_macro::TokenTree::Punct(punc) => {
-                match punc.as_char() {
-                    NEGATIVE_SYMBOL => return true,
-                    _ => {},
+            TokenTree::Punct(punc) => skip_group = punc.as_char().eq(&'#') || punc.as_char().eq(&'!'),
+            TokenTree::Group(_) => if !skip_group {
+                return false;
+            },
+            TokenTree::Ident(ident) => {
+                let ident = ident.to_string();
+                if ident.eq("fn") {
+                    return true;
                 }
+                if !ITEM_QUALIFIERS.contains(&ident.as_str()) && !FN_QUALIFIERS.contains(&ident.as_str()) {
+                    return false;
+                }
+                skip_group = true;
             },
-            _ => {},
+            TokenTree::Literal(_) => {},
         }
     }
 
-    // Default value for documentation
     false
 }
 
-/// Split a token stream at specified token tree
-#[inline(always)]
-pub(crate) fn split_tokenstream_at_operator(stream : TokenStream) -> Option<(char, TokenStream, TokenStream)> {
-
-    // Used to munch tokens on the left
-    let mut left = TokenStream::new();
-
-    // Used to munch tokens on the right
-    let mut right = TokenStream::new();
-
-    // Operator used to separate tokenstream
-    let mut operator: char = '?';
-
-
-    for t in stream.clone() {
-
-        match operator {
-            '?' => {
-                match t.clone() {
-                    proc_macro::TokenTree::Punct(symbol) => {
-                        match symbol.as_char() {
-                                AND_SYMBOL => {    // ALL node
-                                    operator = AND_SYMBOL
-                                }
-                                OR_SYMBOL => {    // ANY node
-                                    operator = OR_SYMBOL
-                                }
-                                // Valid ignored characters
-                                NEGATIVE_SYMBOL | '_' | '-' | ' ' | ':' | '.' => left.extend(TokenStream::from(t)),    // Munch tokens in left hand
-                                    
-                                _ => {
-                                    //err illegal
-                                    panic!("{}", CfgBoostError::InvalidCharacter(String::from(symbol.as_char())).message(&stream.to_string()));
-                                },
-                            }
-                        },
-                    _ => left.extend(TokenStream::from(t)),    // Munch tokens in left hand
+/// Keywords between if_cfg! branches.
+const BRANCH_ELSE : &str = "else";
+const BRANCH_IF : &str = "if";
+
+/// Split if_cfg! tokenstream like `linux { .. } else if windows { .. } else { .. }` into predicates with their block and the else block, if any.
+///
+/// Panic(s)
+/// Will panic! if a predicate is empty or has no block, or if branches aren't separated with `else`.
+pub(crate) fn split_branches(stream : TokenStream) -> (Vec<(TokenStream, Group)>, Option<Group>) {
+
+    let source = stream.to_string();
+    let mut branches : Vec<(TokenStream, Group)> = Vec::new();
+    let mut predicate = TokenStream::new();
+    let mut tokens = stream.into_iter();
+
+    while let Some(t) = tokens.next() {
+        match t {
+            TokenTree::Group(grp) if grp.delimiter() == Delimiter::Brace && !predicate.is_empty() => {
+                branches.push((predicate, grp));
+                predicate = TokenStream::new();
+
+                // 1. Block must be followed by nothing, `else if` or `else { .. }` ending branches.
+                match (tokens.next(), tokens.next()) {
+                    (None, _) => return (branches, None),
+                    (Some(TokenTree::Ident(e)), Some(TokenTree::Ident(i))) if e.to_string().eq(BRANCH_ELSE) && i.to_string().eq(BRANCH_IF) => {},
+                    (Some(TokenTree::Ident(e)), Some(TokenTree::Group(grp))) if e.to_string().eq(BRANCH_ELSE) && grp.delimiter() == Delimiter::Brace && tokens.next().is_none() => return (branches, Some(grp)),
+                    _ => break,
                 }
             },
-            _ => right.extend(TokenStream::from(t)),    // Munch tokens in right hand
-        }        
+            // 2. Predicate tokens until block
+            _ => predicate.extend(TokenStream::from(t)),
+        }
     }
 
-    match operator {
-        '?' => None,    // No split happened
-        _ => Some((operator, left, right)), // Split happened
+    panic!("{}", CfgBoostError::IfCfgSyntaxError.message(&source));
 
-    }
+}
 
+/// Indentation used by pretty printing.
+const PRETTY_INDENT : &str = "    ";
+
+/// Pretty-print tokenstream with attributes, items and statements on their own line and braces content indented.
+pub(crate) fn pretty_print(stream : TokenStream) -> String {
+    let mut pretty = String::new();
+    pretty_print_tokens(stream, 0, &mut pretty);
+    pretty
 }
 
+/// Pretty-print tokens at indentation level into pretty string.
+fn pretty_print_tokens(stream : TokenStream, level : usize, pretty : &mut String) {
 
-/// Split tokenstream in different [item](https://doc.rust-lang.org/reference/items.html) vector tokenstream.
-/// 
-/// An item is defined as all tokens until a ; and/or {}.
-#[inline(always)]
-pub(crate) fn split_items(stream : TokenStream) -> Vec<TokenStream> {
+    // Line being written and flag to glue next token to the previous one
+    let mut line = String::new();
+    let mut glue = false;
 
-    let mut item = TokenStream::new();
-    let mut items : Vec<TokenStream> = Vec::new();
+    // Write line with indentation, then reset it.
+    let flush = |line : &mut String, level : usize, pretty : &mut String| {
+        if !line.is_empty() {
+            pretty.push_str(&PRETTY_INDENT.repeat(level));
+            pretty.push_str(line.trim_end());
+            pretty.push('\n');
+            line.clear();
+        }
+    };
 
     for t in stream {
+        if !line.is_empty() && !glue {
+            line.push(' ');
+        }
+        glue = false;
+
         match &t {
-            proc_macro::TokenTree::Group(grp) => {
-                // Validate if first and last character of group is 
-                match grp.delimiter(){
-                    proc_macro::Delimiter::Brace => {    // End of item. 
-                        item.extend(TokenStream::from(t)); // Add into item tokenstream
-                        items.push(item);   // Push item into vector.
-                        item = TokenStream::new();  // Reset item tokenstream
-                    },
-                    _ => item.extend(TokenStream::from(t)), // Add into item tokenstream
+            TokenTree::Group(grp) if grp.delimiter() == Delimiter::Brace => {     // Indented block on its own lines
+                line.push('{');
+                flush(&mut line, level, pretty);
+                pretty_print_tokens(grp.stream(), level + 1, pretty);
+                line.push('}');
+            },
+            TokenTree::Group(grp) if grp.delimiter() == Delimiter::Bracket && line.trim_end().ends_with('#') => {   // Attribute on its own line
+                line.push_str(&t.to_string());
+                flush(&mut line, level, pretty);
+            },
+            TokenTree::Punct(punc) => {
+                if matches!(punc.as_char(), ';' | ',' | '.') {   // Glued to previous token
+                    line.truncate(line.trim_end().len());
                 }
-            }
-            ,
-            proc_macro::TokenTree::Punct(punc) => {
-                if punc.as_char().eq(&';') { // End of item.
-                    item.extend(TokenStream::from(t)); // Add into item tokenstream
-                    items.push(item);   // Push item into vector.
-                    item = TokenStream::new();  // Reset item tokenstream
-                } else {
-                    item.extend(TokenStream::from(t)); // Add into item tokenstream
+                line.push(punc.as_char());
+                match punc.as_char() {
+                    ';' | ',' => flush(&mut line, level, pretty),
+                    '#' | '.' => glue = true,
+                    _ => glue = punc.spacing() == proc_macro::Spacing::Joint,
                 }
             },
-            _ => item.extend(TokenStream::from(t)), // Add into item tokenstream
+            _ => line.push_str(&t.to_string()),
         }
     }
 
-    items
-}
\ No newline at end of file
+    flush(&mut line, level, pretty);
+
+}
@@ -96,13 +96,23 @@ doc_test_has() {
 # Read documentation and verify that it doesn't contains $2. $1 is used to label test.
 doc_test_hasnt() {
 	source=`cat target/doc/$PRJ_TEST_NAME/index.html`
-	if [[ "$source" == *"stab portability"* ]]; then
+	if [[ "$source" == *"$2"* ]]; then
 		test_failed $1 "$source"
 	else
 		test_passed $1
 	fi
 }
 
+# Read expansion reports in $REPORT_DIR and verify if they contain $2. $1 is used to label test.
+report_test_has() {
+	source=`cat $REPORT_DIR/*.json`
+	if [[ "$source" == *"$2"* ]]; then
+		test_passed $1
+	else
+		test_failed $1 "$source"
+	fi
+}
+
 # Test passes $1 = test name
 test_passed() {
 	TOTAL_TESTS=$((TOTAL_TESTS+1))
@@ -170,8 +180,8 @@ run_test 019.rs "Ensure that all possible cases are being handled by adding a ma
 run_test 020.rs "Test 020 completed!"
 
 
-# T21 Test all predefined predicates (value:predicate)
-run_test 021.rs "Test 021 completed!"
+# T21 Test all predefined predicates (value:predicate) with values and features validation disabled.
+cfg_boost_check_values=false cfg_boost_check_features=false run_test 021.rs "Test 021 completed!"
 
 
 # T22 Custom predicates missing
@@ -309,6 +319,26 @@ else
 	test_passed "052REL"
 fi
 
+# Warn instead of ignoring
+cp -r "../tests/rs/071.rs" "src/main.rs"
+echo "release_modifier_behaviour = \"warn\"" > cfg_boost.toml
+result="$(cargo run --release 2>&1)"
+if [[ "$result" == *"are ignored during release compilation!"* && "$result" == *"Test 071 ignored!"* ]]; then
+	test_passed "053REL"
+else
+	test_failed "053REL" "$result"
+fi
+
+# Modifiers follow debug assertions of the compiled crate, not of cfg_boost.
+echo "release_modifier_behaviour = \"panic\"" > cfg_boost.toml
+result="$(CARGO_PROFILE_RELEASE_DEBUG_ASSERTIONS=true cargo run --release 2>&1)"
+if [[ "$result" == *"Test 071 activated!"* ]]; then
+	test_passed "054REL"
+else
+	test_failed "054REL" "$result"
+fi
+rm "cfg_boost.toml"
+
 #T53 Modifier + on target_cfg!
 run_test 053.rs "Test 053 completed!"
 
@@ -340,6 +370,230 @@ run_test 061.rs "Test 061 completed!"
 run_test 062.rs "Macro panicked because some arm have the"
 run_test 063.rs "Test 063 completed!"
 
+#T64 Per-crate configuration with cfg_boost.toml and [package.metadata.cfg_boost]
+cp -r "../tests/rs/cfg_boost.toml" "cfg_boost.toml"
+echo "[package.metadata.cfg_boost.aliases]" >> Cargo.toml
+echo "bird = \"unix:_ | windows:_ | wasm:_\"" >> Cargo.toml
+run_test 064.rs "Test 064 completed completed!"
+
+#T65 Configuration file modification recompile crate.
+run_test 065.rs "Test 065 completed!"
+echo "[aliases]" > cfg_boost.toml
+echo "fish = \"foo:c8\"" >> cfg_boost.toml
+result="$(cargo run 2>&1)"
+if [[ "$result" == *"Test 065 tracked!"* ]]; then
+	test_passed "065TRK"
+else
+	test_failed "065TRK" "$result"
+fi
+rm "cfg_boost.toml"
+
+#T66 Availability notes generated in stable documentation.
+echo "availability_notes = true" > cfg_boost.toml
+run_test 066.rs "Test 066 completed!"
+generate_doc 0
+doc_test_has "DOC017" "Available on <strong>linux</strong> or <strong>wasm</strong> only."
+rm "cfg_boost.toml"
+
+#T67 Doc-stub mode documents items using platform-only dependencies.
+echo "autodoc = true" > cfg_boost.toml
+echo "doc_stub = true" >> cfg_boost.toml
+run_test 067.rs "Test 067 completed!"
+generate_doc 0
+doc_test_has "DOC018" "windows_only_stub"
+doc_test_has "DOC019" "WindowsOnlyStub"
+run_test 067_paths.rs "Test 067 completed!"
+generate_doc 0
+doc_test_has "DOC025" "windows_paths_stub"
+doc_test_has "DOC026" "MAX_PATH_LEN"
+doc_test_has "DOC027" "STARTUP_ERROR"
+doc_test_hasnt "DOC028" "LAST_ERROR"
+rm "cfg_boost.toml"
+
+#T68 Items with the same name in many arms documented once with union of predicates.
+echo "autodoc = true" > cfg_boost.toml
+run_test 068.rs "Test 068 linux!"
+generate_doc 1
+doc_test_has "DOC020" "shared_fn"
+doc_test_has "DOC021" 'Available on Linux or Windows or `wasm` only'
+rm "cfg_boost.toml"
+
+#T69 Options header disable autodoc and docs.rs tags for one invocation.
+echo "autodoc = true" > cfg_boost.toml
+run_test 069.rs "Test 069 completed!"
+generate_doc 1
+doc_test_has "DOC022" "header_no_tag"
+doc_test_hasnt "DOC023" "header_no_autodoc"
+doc_test_hasnt "DOC024" "meta_header_no_autodoc"
+rm "cfg_boost.toml"
+
+#T70 Invalid option in options header, including options that don't apply to the macro.
+run_test 070.rs "Invalid option \`autodocs\` in target_cfg! header! Options of target_cfg! are \`autodoc\`, \`docsrs_tag\`, \`doc_stub\`, \`availability_notes\`, \`dump\`."
+run_test 070_match.rs "Invalid option \`autodoc\` in match_cfg! header! Options of match_cfg! are \`dump\`, \`checked\`."
+
+#T72 Arm labels forced or disabled by environment.
+run_test 072.rs "Test 072 opengl linux!"
+CFG_BOOST_FORCE="vk,win" CFG_BOOST_DISABLE="gl" run_test 072.rs "Test 072 vulkan windows!"
+CFG_BOOST_FORCE="lx" CFG_BOOST_DISABLE="lx" run_test 072.rs "cannot be in both"
+
+#T73 CfgBoostError::LabelSyntaxError
+run_test 073.rs "Arm label syntax error"
+
+#T74 Trace modifier `?` and CFG_BOOST_TRACE print arms without failing.
+run_test 074.rs "Macro traced by"
+run_test 074.rs "Test 074 completed!"
+CFG_BOOST_TRACE="1" run_test 074.rs "Arm : windows"
+
+#T75 Dump option and CFG_BOOST_DUMP print generated items without failing.
+run_test 075.rs "target_cfg! expansion dumped by"
+run_test 075.rs "Test 075 completed!"
+CFG_BOOST_DUMP="1" run_test 075.rs "// Exclusion : all(all(), not(windows))"
+
+#T76 JSON expansion reports written to CFG_BOOST_REPORT_DIR.
+REPORT_DIR="target/cfg_boost_reports"
+rm -rf "$REPORT_DIR"
+CFG_BOOST_REPORT_DIR="$REPORT_DIR" run_test 076.rs "Test 076 completed!"
+report_test_has "REP001" '"macro":"target_cfg!"'
+report_test_has "REP002" '"label":"gl","type":"Simplified","modifier":"None"'
+report_test_has "REP003" '{"keyword":"struct","name":"ReportedStruct"}'
+report_test_has "REP004" '"macro":"match_cfg!"'
+report_test_has "REP005" '"macro":"meta_cfg"'
+report_test_has "REP006" '{"alias":"linux","node":{"leaf":"linux : os","predicate":"target_os = \"linux\""}}'
+rm -rf "$REPORT_DIR"
+
+#T77 Traced arms explained for target or cfg file given by CFG_BOOST_EXPLAIN.
+run_test 077.rs "Test 077 completed!"
+CFG_BOOST_EXPLAIN="x86_64-pc-windows-msvc" run_test 077.rs '[false] `linux` => `target_os = "linux"` (target has `target_os` = "windows")'
+CFG_BOOST_EXPLAIN="x86_64-pc-windows-msvc" run_test 077.rs 'Generated `all(all(),windows)` is `true`'
+CFG_BOOST_EXPLAIN="../tests/rs/077.cfg" run_test 077.rs '[false] `x86_64` => `target_arch = "x86_64"` (target has `target_arch` = "aarch64")'
+CFG_BOOST_EXPLAIN="../tests/rs/077.cfg" run_test 077.rs 'Generated `all(target_os = "linux",not(target_arch = "x86_64"))` is `true`'
+CFG_BOOST_EXPLAIN="no-such-target" run_test 077.rs "set with \`CFG_BOOST_EXPLAIN\` cannot be read!"
+
+#T78 Unknown values of predefined predicates rejected with suggestions, unless added in values table.
+echo "check_values = true" > cfg_boost.toml
+run_test 078.rs 'Unknown `os` value in `linx : os`! Did you mean `linux`?'
+echo "[values]" >> cfg_boost.toml
+echo "os = \"linx, other\"" >> cfg_boost.toml
+run_test 078.rs "Test 078 completed!"
+rm "cfg_boost.toml"
+
+#T79 Features validated against Cargo.toml with suggestions, unless added in values table.
+echo "check_features = true" > cfg_boost.toml
+echo "" >> Cargo.toml
+echo "[features]" >> Cargo.toml
+echo "gl = []" >> Cargo.toml
+run_test 079.rs 'Unknown feature in `glx : ft`! It isn'"'"'t in `[features]` or optional dependencies of Cargo.toml. Did you mean `gl`?'
+echo "[values]" >> cfg_boost.toml
+echo "ft = \"glx\"" >> cfg_boost.toml
+run_test 079.rs "Test 079 completed!"
+rm "cfg_boost.toml"
+
+#T80 Custom predicates and aliases declared with cargo::rustc-check-cfg by cfg_boost_build in build.rs, which fails on malformed configuration.
+run_test 080.rs 'unexpected `cfg` condition name: `custom1`'
+cp -r "../tests/rs/080_build.rs" "build.rs"
+echo "" >> Cargo.toml
+echo "[build-dependencies]" >> Cargo.toml
+echo "cfg_boost_build = { path = \"../cfg_boost_build\" }" >> Cargo.toml
+run_test 080.rs "Test 080 completed!"
+result="$(cargo build 2>&1)"
+if [[ "$result" == *"unexpected \`cfg\` condition"* ]]; then
+	test_failed "080CHK" "$result"
+else
+	test_passed "080CHK"
+fi
+result="$(env "cfg_boost-fish=shark:_" cargo build 2>&1)"
+output="$(cat target/debug/build/*/output 2>&1)"
+if [[ "$output" == *"rustc-check-cfg=cfg(shark)"* && "$output" == *"rerun-if-env-changed=cfg_boost-fish"* && "$output" != *"rerun-if-changed=cfg_boost.toml"* ]]; then
+	test_passed "080ENV"
+else
+	test_failed "080ENV" "$output"
+fi
+# Creating cfg_boost.toml isn't detected until build.rs changes.
+echo "[aliases" > cfg_boost.toml
+touch build.rs
+result="$(cargo build 2>&1)"
+if [[ "$result" == *"failed to run custom build command"* && "$result" == *"cfg_boost.toml\` is malformed at line"* ]]; then
+	test_passed "080ERR"
+else
+	test_failed "080ERR" "$result"
+fi
+rm "cfg_boost.toml"
+rm "build.rs"
+
+#T81 match_target! checks arms against the target with CARGO_CFG_* and CARGO_FEATURE_* in build.rs.
+cp -r "../tests/rs/081_build.rs" "build.rs"
+echo "cfg_boost = { path = \"$(ls -d cfg_boost-*)\" }" >> Cargo.toml
+run_test 081.rs "Test 081 completed! linux"
+run_test 081.rs "Test 081 completed! gl linux" --features gl
+rm "build.rs"
+
+#T82 is_cfg! and if_cfg! with simplified syntax.
+run_test 082.rs "Test 082 completed!"
+run_test 082_err.rs "if_cfg! syntax error in"
+
+#T83 match_cfg! checked mode lowered to an `if cfg!()` chain type-checking every arm.
+run_test 083.rs "Test 083 completed!"
+run_test 083.rs "} else if cfg ! (all(target_os = \"linux\",not(any(target_os = \"android\",target_os = \"ios\")))) {"
+run_test 083_err.rs "mismatched types"
+
+#T84 cfg_label! and cfg_predicate! name the first matching arm.
+run_test 084.rs 'Test 084 completed! [linux & !aarch64:ar] [kernel] [_] [any(target_os = "linux", any(windows, target_os = "macos"))] [linux:os|  macos:os]'
+
+#T85 cfg_boost_active! lists predefined aliases, configured aliases like `bird` of T64 and features with their cfg! value.
+run_test 085.rs "Test 085 completed! linux unix debug desktop bird"
+run_test 085.rs "Test 085 completed! linux unix debug desktop bird gl" --features gl
+
+#T85 Aliases of environment variables are tracked, invalid aliases are reported.
+touch "src/main.rs"
+result="$(env "cfg_boost-fish=linux:os" cargo run 2>&1)"
+if [[ "$result" == *"Test 085 completed! linux unix debug desktop bird fish"* ]]; then
+	test_passed "085ENV"
+else
+	test_failed "085ENV" "$result"
+fi
+result="$(env "cfg_boost-fish=windows:_" cargo run 2>&1)"
+if [[ "$result" == *"Test 085 completed! linux unix debug desktop bird"* && "$result" != *"fish"* ]]; then
+	test_passed "085TRK"
+else
+	test_failed "085TRK" "$result"
+fi
+touch "src/main.rs"
+result="$(env "cfg_boost-fish=linux:os |" cargo run 2>&1)"
+if [[ "$result" == *"Alias \`fish\` cannot be listed by cfg_boost_active!"* ]]; then
+	test_passed "085ERR"
+else
+	test_failed "085ERR" "$result"
+fi
+
+#T86 Configuration files, environment and release modifier warning inside an impl.
+echo "[aliases]" > cfg_boost.toml
+echo "fish = \"linux:os\"" >> cfg_boost.toml
+run_test 086.rs "Test 086 completed linux fish!"
+echo "[aliases]" > cfg_boost.toml
+echo "fish = \"windows:os\"" >> cfg_boost.toml
+result="$(cargo run 2>&1)"
+if [[ "$result" == *"Test 086 tracked linux no fish!"* ]]; then
+	test_passed "086TRK"
+else
+	test_failed "086TRK" "$result"
+fi
+result="$(CFG_BOOST_FORCE="win" CFG_BOOST_DISABLE="lx" cargo run 2>&1)"
+if [[ "$result" == *"Test 086 tracked forced no fish!"* ]]; then
+	test_passed "086ENV"
+else
+	test_failed "086ENV" "$result"
+fi
+echo "release_modifier_behaviour = \"warn\"" > cfg_boost.toml
+run_test 086_warn.rs "Test 086 activated!"
+result="$(cargo run --release 2>&1)"
+if [[ "$result" == *"are ignored during release compilation!"* && "$result" == *"Test 086 ignored!"* ]]; then
+	test_passed "086REL"
+else
+	test_failed "086REL" "$result"
+fi
+run_test 086_err.rs "cannot warn during release compilation in arms of target_cfg! without a function body"
+rm "cfg_boost.toml"
+
 
 #########
 # TOTAL #
@@ -141,13 +141,13 @@ printf "\033c"
 # 5. Go to cfg_boost root
 cd ..
 
-# 6. Generate target_cfg package, allowing dirty
-cargo package --allow-dirty
+# 6. Generate target_cfg package with its parser library, allowing dirty
+cargo package --workspace --allow-dirty
 
 # 7. Get package version from Cargo.toml
 package_version=""
 while read line; do    
-    if [[ "$line" == *"version ="* ]]; then
+    if [[ "$line" == "version ="* ]]; then
     	package_version=$(remove_quotes $(echo $line | awk '{print $3}'))
 	fi
 done < Cargo.toml
@@ -163,6 +163,11 @@ cp -r "target/package/cfg_boost-$package_version" "$PRJ_TEST_NAME/cfg_boost-$pac
 echo "cfg_boost = { path = \"cfg_boost-$package_version\", version = \"$package_version\" }" >> $PRJ_TEST_NAME/Cargo.toml
 echo "" >> $PRJ_TEST_NAME/Cargo.toml
 
+# 10.1 Parser library isn't published with this version, use local one.
+echo "[patch.crates-io]" >> $PRJ_TEST_NAME/Cargo.toml
+echo "cfg_boost_parser = { path = \"../cfg_boost_parser\" }" >> $PRJ_TEST_NAME/Cargo.toml
+echo "" >> $PRJ_TEST_NAME/Cargo.toml
+
 
 # 11. Move to test project folder
 cd $PRJ_TEST_NAME
@@ -0,0 +1,18 @@
+// Test 064 : Per-crate configuration file and Cargo.toml metadata.
+use cfg_boost::match_cfg;
+
+fn main() {
+    // fish alias from config.toml is overridden by cfg_boost.toml.
+    let file = match_cfg!{
+        fish => "completed",
+        _ => "failed"
+    };
+
+    // bird alias comes from [package.metadata.cfg_boost.aliases].
+    let metadata = match_cfg!{
+        bird => "completed!",
+        _ => "failed!"
+    };
+
+    println!("Test 064 {} {}", file, metadata);
+}
@@ -0,0 +1,17 @@
+// Test 065 : Configuration file changes are tracked.
+use cfg_boost::target_cfg;
+
+target_cfg!{
+    fish => {
+        pub struct Status;
+        pub fn status() -> &'static str { "completed" }
+    },
+    !fish => {
+        pub struct Status;
+        pub fn status() -> &'static str { "tracked" }
+    },
+}
+
+fn main() {
+    println!("Test 065 {}!", status());
+}
@@ -0,0 +1,12 @@
+// Test 066 : Availability notes for stable documentation.
+use cfg_boost::target_cfg;
+
+target_cfg!{
+    linux | wasm => {
+        pub fn linux_or_wasm() {}
+    },
+}
+
+fn main() {
+    println!("Test 066 completed!");
+}
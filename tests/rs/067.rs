@@ -0,0 +1,22 @@
+// Test 067 : Doc-stub mode documents platform-only code without compiling it.
+use cfg_boost::target_cfg;
+
+target_cfg!{
+    windows => {
+        /// Use a dependency only available on windows.
+        pub fn windows_only_stub() -> u32 { windows_sys::Win32::Foundation::GetLastError() }
+
+        pub struct WindowsOnlyStub;
+
+        impl WindowsOnlyStub {
+            pub fn handle(&self) -> u32 { windows_sys::Win32::Foundation::GetLastError() }
+        }
+    },
+    linux => {
+        pub fn linux_only_stub() -> u32 { 67 }
+    }
+}
+
+fn main() {
+    println!("Test 067 completed!");
+}
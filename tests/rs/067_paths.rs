@@ -0,0 +1,35 @@
+// Test 067 : Doc-stub mode leaves imports out and stubs modules and initializers using platform-only paths.
+use cfg_boost::target_cfg;
+
+target_cfg!{
+    windows => {
+        extern crate windows_sys;
+        use std::os::windows::ffi::OsStrExt;
+
+        mod ffi;
+
+        /// Maximum length of a path.
+        pub const MAX_PATH_LEN : usize = 260;
+
+        pub const LAST_ERROR : u32 = windows_sys::Win32::Foundation::ERROR_SUCCESS;
+
+        /// Error code read at startup.
+        pub static STARTUP_ERROR : u32 = windows_sys::Win32::Foundation::ERROR_SUCCESS;
+
+        /// Encode a path in UTF-16.
+        pub fn windows_paths_stub(path : &std::ffi::OsStr) -> Vec<u16> { path.encode_wide().collect() }
+
+        pub struct WindowsPathsStub;
+
+        impl WindowsPathsStub {
+            pub const SEPARATOR : u16 = ffi::SEPARATOR;
+        }
+    },
+    linux => {
+        pub fn linux_paths_stub() -> u32 { 67 }
+    }
+}
+
+fn main() {
+    println!("Test 067 completed!");
+}
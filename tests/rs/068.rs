@@ -0,0 +1,22 @@
+// Test 068 : Items declared with the same name in many arms are documented once.
+#![cfg_attr(docsrs, feature(doc_cfg))]
+use cfg_boost::target_cfg;
+
+target_cfg!{
+    linux => {
+        /// Shared function for linux.
+        pub fn shared_fn() -> &'static str { "linux" }
+    },
+    windows => {
+        /// Shared function for windows.
+        pub fn shared_fn() -> &'static str { "windows" }
+    },
+    wasm => {
+        /// Shared function for wasm.
+        pub fn shared_fn() -> &'static str { "wasm" }
+    },
+}
+
+fn main() {
+    println!("Test 068 {}!", shared_fn());
+}
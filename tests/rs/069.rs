@@ -0,0 +1,24 @@
+// Test 069 : Options header disable autodoc and docs.rs tags for one invocation.
+#![cfg_attr(docsrs, feature(doc_cfg))]
+use cfg_boost::{ target_cfg, meta_cfg };
+
+target_cfg!{
+    #![autodoc(false)]
+    windows => {
+        pub fn header_no_autodoc() {}
+    },
+}
+
+target_cfg!{
+    #![docsrs_tag(false)]
+    linux => {
+        pub fn header_no_tag() {}
+    },
+}
+
+#[meta_cfg(#![autodoc(false)] windows)]
+pub fn meta_header_no_autodoc() {}
+
+fn main() {
+    println!("Test 069 completed!");
+}
@@ -0,0 +1,13 @@
+// Test 070 : Invalid option in options header.
+use cfg_boost::target_cfg;
+
+target_cfg!{
+    #![autodocs(false)]
+    linux => {
+        pub fn invalid_option() {}
+    },
+}
+
+fn main() {
+    println!("Test 070 completed!");
+}
@@ -0,0 +1,10 @@
+// Test 070 : Documentation options don't apply to match_cfg!.
+use cfg_boost::match_cfg;
+
+fn main() {
+    match_cfg!{
+        #![autodoc(false)]
+        linux => println!("linux"),
+        _ => println!("Test 070 completed!"),
+    }
+}
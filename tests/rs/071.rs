@@ -0,0 +1,11 @@
+// Test 071 : Modifiers follow the profile of the compiled crate.
+use cfg_boost::match_cfg;
+
+fn main() {
+    let modifier = match_cfg!{
+        + windows => "activated",
+        _ => "ignored",
+    };
+
+    println!("Test 071 {}!", modifier);
+}
@@ -0,0 +1,21 @@
+// Test 072 : Arm labels forced or disabled by environment.
+use cfg_boost::{ target_cfg, match_cfg };
+
+target_cfg!{
+    'vk: windows => {
+        pub fn backend() -> &'static str { "vulkan" }
+    },
+    'gl: linux => {
+        pub fn backend() -> &'static str { "opengl" }
+    },
+}
+
+fn main() {
+    let os = match_cfg!{
+        'lx: linux => "linux",
+        'win: windows => "windows",
+        _ => "other",
+    };
+
+    println!("Test 072 {} {}!", backend(), os);
+}
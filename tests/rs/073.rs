@@ -0,0 +1,12 @@
+// Test 073 : CfgBoostError::LabelSyntaxError
+use cfg_boost::target_cfg;
+
+target_cfg!{
+    'gl linux => {
+        pub fn backend() -> &'static str { "opengl" }
+    },
+}
+
+fn main() {
+    println!("Test 073 completed!");
+}
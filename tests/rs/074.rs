@@ -0,0 +1,20 @@
+// Test 074 : Trace modifier doesn't fail compilation.
+use cfg_boost::{ target_cfg, match_cfg };
+
+target_cfg!{
+    ? linux => {
+        pub fn traced() -> &'static str { "completed" }
+    },
+    windows => {
+        pub fn traced() -> &'static str { "completed" }
+    },
+}
+
+fn main() {
+    let test = match_cfg!{
+        ?+ linux => "074",
+        _ => "000",
+    };
+
+    println!("Test {} {}!", test, traced());
+}
@@ -0,0 +1,22 @@
+// Test 075 : Dump generated items and match_cfg! exclusion predicates.
+use cfg_boost::{ target_cfg, match_cfg };
+
+target_cfg!{
+    #![dump]
+    linux => {
+        pub struct DumpedStruct;
+        pub fn dumped_fn() -> &'static str { "075" }
+    },
+    windows => {
+        pub fn dumped_fn() -> &'static str { "075" }
+    },
+}
+
+fn main() {
+    let test = match_cfg!{
+        windows => "000",
+        _ => dumped_fn(),
+    };
+
+    println!("Test {} completed!", test);
+}
@@ -0,0 +1,24 @@
+// Test 076 : JSON expansion reports written to CFG_BOOST_REPORT_DIR.
+use cfg_boost::{ target_cfg, match_cfg, meta_cfg };
+
+target_cfg!{
+    'gl: linux => {
+        pub struct ReportedStruct;
+        pub fn reported_fn() -> &'static str { "076" }
+    },
+    windows => {
+        pub fn reported_fn() -> &'static str { "076" }
+    },
+}
+
+#[meta_cfg(linux | windows)]
+pub fn reported_meta_fn() {}
+
+fn main() {
+    let test = match_cfg!{
+        windows => "000",
+        _ => reported_fn(),
+    };
+
+    println!("Test {} completed!", test);
+}
@@ -0,0 +1,20 @@
+// Test 077 : Explain traced arms for target given by CFG_BOOST_EXPLAIN.
+use cfg_boost::{ target_cfg, match_cfg };
+
+target_cfg!{
+    ?linux & !x86_64:ar => {
+        pub fn explained_fn() -> &'static str { "077" }
+    },
+    !linux | x86_64:ar => {
+        pub fn explained_fn() -> &'static str { "077" }
+    },
+}
+
+fn main() {
+    let test = match_cfg!{
+        ?windows => "000",
+        _ => explained_fn(),
+    };
+
+    println!("Test {} completed!", test);
+}
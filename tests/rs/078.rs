@@ -0,0 +1,15 @@
+// Test 078 : Unknown values of predefined predicates are rejected unless added in values table.
+use cfg_boost::{ target_cfg };
+
+target_cfg!{
+    linx:os => {
+        pub fn value_fn() -> &'static str { "000" }
+    },
+    !linx:os => {
+        pub fn value_fn() -> &'static str { "078" }
+    },
+}
+
+fn main() {
+    println!("Test {} completed!", value_fn());
+}
@@ -0,0 +1,15 @@
+// Test 079 : Features are validated against Cargo.toml unless added in values table.
+use cfg_boost::{ target_cfg };
+
+target_cfg!{
+    gl:ft | glx:ft => {
+        pub fn feature_fn() -> &'static str { "000" }
+    },
+    !(gl:ft | glx:ft) => {
+        pub fn feature_fn() -> &'static str { "079" }
+    },
+}
+
+fn main() {
+    println!("Test {} completed!", feature_fn());
+}
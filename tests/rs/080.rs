@@ -0,0 +1,15 @@
+// Test 080 : Custom predicates and aliases declared with check-cfg by cfg_boost_build.
+use cfg_boost::{ target_cfg };
+
+target_cfg!{
+    fish | foo:c1 => {
+        pub fn check_cfg_fn() -> &'static str { "000" }
+    },
+    !(fish | foo:c1) => {
+        pub fn check_cfg_fn() -> &'static str { "080" }
+    },
+}
+
+fn main() {
+    println!("Test {} completed!", check_cfg_fn());
+}
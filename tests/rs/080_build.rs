@@ -0,0 +1,4 @@
+// Build script of test 080 declaring custom predicates and aliases.
+fn main() {
+    cfg_boost_build::emit_check_cfg();
+}
@@ -0,0 +1,5 @@
+// Test 081 : match_target! checks arms against the target in build.rs.
+
+fn main() {
+    println!("Test 081 completed! {}", env!("MATCH_TARGET"));
+}
@@ -0,0 +1,12 @@
+// Build script of test 081 : match_target! checks arms against CARGO_CFG_* and CARGO_FEATURE_* environment variables.
+use cfg_boost::match_target;
+
+fn main() {
+    let target = match_target!{
+        linux & gl:ft => "gl linux",
+        #[cfg(target_os = "linux")] => "linux",
+        windows | macos => "desktop",
+        _ => "other",
+    };
+    println!("cargo::rustc-env=MATCH_TARGET={}", target);
+}
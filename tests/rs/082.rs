@@ -0,0 +1,22 @@
+// Test 082 : is_cfg! and if_cfg! with simplified syntax.
+use cfg_boost::{ is_cfg, if_cfg };
+
+fn main() {
+    // is_cfg! is a bool like cfg!
+    let linux = is_cfg!(linux | android:os);
+    let windows = is_cfg!(windows & !linux);
+
+    // if_cfg! type-checks every branch
+    let code = if_cfg!{
+        windows {
+            "000"
+        } else if linux & !mobile {
+            "082"
+        } else {
+            "001"
+        }
+    };
+
+    if_cfg!{ !debug { println!("{} {} {}", code, linux, windows); } }
+    if_cfg!{ linux { println!("Test {} completed!", code); } else { println!("Not linux"); } }
+}
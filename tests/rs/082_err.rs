@@ -0,0 +1,6 @@
+// Test 082 : if_cfg! branches must be separated with else.
+use cfg_boost::{ if_cfg };
+
+fn main() {
+    if_cfg!{ linux { println!("linux"); } windows { println!("windows"); } }
+}
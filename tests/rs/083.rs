@@ -0,0 +1,17 @@
+// Test 083 : match_cfg! checked mode type-checks every arm on every platform.
+use cfg_boost::{ match_cfg };
+
+fn main() {
+    let code = match_cfg!{
+        #![checked, dump]
+        windows => "000",
+        linux & !mobile => "083",
+        _ => "001",
+    };
+
+    match_cfg!{
+        #![checked]
+        ?linux => println!("Test {} completed!", code),
+        _ => println!("Not linux {}", code),
+    }
+}
@@ -0,0 +1,11 @@
+// Test 083 : match_cfg! checked mode type-checks arms of other platforms.
+use cfg_boost::{ match_cfg };
+
+fn main() {
+    let code : &str = match_cfg!{
+        #![checked]
+        windows => 83,
+        _ => "083",
+    };
+    println!("Test {} completed!", code);
+}
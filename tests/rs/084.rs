@@ -0,0 +1,12 @@
+// Test 084 : cfg_label! and cfg_predicate! name the first matching arm.
+use cfg_boost::{ cfg_label, cfg_predicate };
+
+fn main() {
+    let label : &str = cfg_label!{ mobile, linux & !aarch64:ar, windows, _ };
+    let named = cfg_label!{ 'kernel: linux | android, _ };
+    let legacy = cfg_label!{ #[cfg(windows)], _, };
+    let predicate : &str = cfg_predicate!{ mobile, desktop, _ };
+    let written = cfg_label!{ windows:_, linux:os|  macos:os, _ };
+
+    println!("Test 084 completed! [{}] [{}] [{}] [{}] [{}]", label, named, legacy, predicate, written);
+}
@@ -0,0 +1,13 @@
+// Test 085 : cfg_boost_active! lists aliases and features with their cfg! value.
+use cfg_boost::cfg_boost_active;
+
+fn main() {
+    let active : &[(&str, bool)] = cfg_boost_active!();
+
+    // Inactive entries are listed too
+    assert!(active.contains(&("windows", false)));
+    assert!(active.contains(&("bird", true)));
+
+    let names : Vec<&str> = active.iter().filter(|(_, active)| *active).map(|(name, _)| *name).collect();
+    println!("Test 085 completed! {}", names.join(" "));
+}
@@ -0,0 +1,34 @@
+// Test 086 : Configuration file and environment changes are tracked inside an impl.
+use cfg_boost::{ target_cfg, meta_cfg };
+
+pub struct Status;
+
+impl Status {
+    target_cfg!{
+        fish => {
+            pub fn status() -> &'static str { "completed" }
+        },
+        !fish => {
+            pub fn status() -> &'static str { "tracked" }
+        },
+    }
+
+    target_cfg!{
+        'win: windows => {
+            pub fn forced() -> &'static str { "forced" }
+        },
+        'lx: linux => {
+            pub fn forced() -> &'static str { "linux" }
+        },
+    }
+
+    #[meta_cfg(fish)]
+    pub const NAME : &'static str = "fish";
+
+    #[meta_cfg(!fish)]
+    pub const NAME : &'static str = "no fish";
+}
+
+fn main() {
+    println!("Test 086 {} {} {}!", Status::status(), Status::forced(), Status::NAME);
+}
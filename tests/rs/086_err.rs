@@ -0,0 +1,23 @@
+// Test 086 : Release modifier warning cannot be generated in arms without body inside an impl.
+use cfg_boost::target_cfg;
+
+pub trait Kind {
+    type Id;
+}
+
+pub struct Modifier;
+
+impl Kind for Modifier {
+    target_cfg!{
+        + windows => {
+            type Id = u16;
+        },
+        - linux => {
+            type Id = u8;
+        },
+    }
+}
+
+fn main() {
+    println!("Test 086 {}!", std::mem::size_of::<<Modifier as Kind>::Id>());
+}
@@ -0,0 +1,19 @@
+// Test 086 : Release modifier warning inside an impl.
+use cfg_boost::target_cfg;
+
+pub struct Modifier;
+
+impl Modifier {
+    target_cfg!{
+        + windows => {
+            pub fn name() -> &'static str { "activated" }
+        },
+        - linux => {
+            pub fn name() -> &'static str { "ignored" }
+        },
+    }
+}
+
+fn main() {
+    println!("Test 086 {}!", Modifier::name());
+}
@@ -0,0 +1,73 @@
+use cfg_boost_parser::{tokenize, Settings};
+
+use super::{CfgTables, template_cfg, TemplateCfg};
+
+/// Test check-cfg of custom predicates with any value and bare names of aliases
+#[test]
+fn check_cfg_any_values() {
+    let settings = Settings::parse(r#"
+[aliases]
+fish = "foo:c1 | bar:_ | unix:_ | linux:os"
+
+[predicates]
+c1 = "custom1 = \"{}\""
+c2 = "all(custom2 = \"{}\", unix, custom_flag)"
+"#).unwrap();
+
+    assert_eq!(CfgTables::new(&settings, std::iter::empty()).check_cfg(), vec![
+        "cargo::rustc-check-cfg=cfg(bar)",
+        "cargo::rustc-check-cfg=cfg(custom1, values(any()))",
+        "cargo::rustc-check-cfg=cfg(custom2, values(any()))",
+        "cargo::rustc-check-cfg=cfg(custom_flag)",
+    ]);
+}
+
+/// Test check-cfg values restricted by values table, completed by labels of aliases
+#[test]
+fn check_cfg_restricted_values() {
+    let settings = Settings::parse(r#"
+[aliases]
+fish = "foo:c1 & !baz:c1"
+
+[predicates]
+c1 = "custom1 = \"{}\""
+c3 = "{}"
+myos = "target_os = \"{}\""
+
+[values]
+c1 = "qux, quux"
+c3 = "flag_a, flag_b"
+"#).unwrap();
+
+    assert_eq!(CfgTables::new(&settings, std::iter::empty()).check_cfg(), vec![
+        "cargo::rustc-check-cfg=cfg(custom1, values(\"baz\", \"foo\", \"quux\", \"qux\"))",
+        "cargo::rustc-check-cfg=cfg(flag_a)",
+        "cargo::rustc-check-cfg=cfg(flag_b)",
+    ]);
+}
+
+/// Test that environment variables complete configuration files without overriding them
+#[test]
+fn check_cfg_environment() {
+    let settings = Settings::parse("[predicates]\nc1 = \"custom1 = \\\"{}\\\"\"\n").unwrap();
+    let vars = [("cfg_boost_predicate-c1", "overridden = \"{}\""), ("cfg_boost_predicate-c2", "custom2 = \"{}\""), ("cfg_boost-fish", "foo:c2 | bar:_"),
+        ("cfg_boost_values-c2", "foo"), ("cfg_boost_autodoc", "false")];
+
+    let tables = CfgTables::new(&settings, vars.iter().map(|(k, v)| (String::from(*k), String::from(*v))));
+    assert_eq!(tables.check_cfg(), vec![
+        "cargo::rustc-check-cfg=cfg(bar)",
+        "cargo::rustc-check-cfg=cfg(custom1, values(any()))",
+        "cargo::rustc-check-cfg=cfg(custom2, values(\"foo\"))",
+    ]);
+
+    // Environment variables of tables are tracked, even overridden ones
+    assert_eq!(tables.env_keys.iter().map(String::as_str).collect::<Vec<_>>(), vec!["cfg_boost-fish", "cfg_boost_predicate-c1", "cfg_boost_predicate-c2", "cfg_boost_values-c2"]);
+}
+
+/// Test cfg of predicate templates tokens
+#[test]
+fn template_cfg_tokens() {
+    assert_eq!(template_cfg(&tokenize("any(key = \"{}\", name_{}, not(flag))").unwrap()), vec![TemplateCfg::KeyValue(String::from("key"), String::from("{}")),
+        TemplateCfg::Name(String::from("name_{}")), TemplateCfg::Name(String::from("flag"))]);
+    assert_eq!(template_cfg(&tokenize("{}").unwrap()), vec![TemplateCfg::Name(String::from("{}"))]);
+}
@@ -1,7 +1,17 @@
 use std::time::Instant;
 
-use super::ALIASES;
-use super::{get_cfg_boost_predicate, PREDICATES};
+use cfg_boost_parser::{ALIASES, PREDICATES, PREDICATE_VALUES, ParseError, Settings, resolve_alias, resolve_predicate};
+use cfg_boost_parser::{ENV_KEY_ALIAS, ENV_KEY_PREDICATE, ENV_KEY_VALUES, PREDICATE_PLACEHOLDER};
+
+/// Resolve predicate with configuration of crate, like macros do.
+fn get_cfg_boost_predicate(tokens : &str) -> Result<String, ParseError> {
+    resolve_predicate(super::get_config().as_ref(), tokens)
+}
+
+/// Resolve alias with configuration of crate, like macros do.
+fn get_cfg_boost_alias(label : &str) -> Result<String, ParseError> {
+    resolve_alias(super::get_config().as_ref(), label)
+}
 
 /// Test all predefined aliases
 #[test]
@@ -29,7 +39,7 @@ fn custom_aliases() {
 
     // 2. Set custom aliases in env.
     for alias in &aliases {
-        std::env::set_var(format!("{}{}", super::ENV_KEY_ALIAS, alias.0), alias.1);
+        std::env::set_var(format!("{}{}", ENV_KEY_ALIAS, alias.0), alias.1);
     }
 
     // 3. Test each custom alias
@@ -59,7 +69,7 @@ fn stress_performance_aliases() {
         let alias = ALIASES[i & (ALIASES.len() - 1)];
 
         // Test each alias picked
-        match super::get_cfg_boost_alias(alias.0) {
+        match get_cfg_boost_alias(alias.0) {
             Ok(result) => {
                 // If result != value, panic!
                 if result.ne(alias.1) {
@@ -81,6 +91,9 @@ fn stress_performance_aliases() {
 /// Test parse_cfg_predicate WILDCARD_PREDICATE
 #[test]
 fn predefined_predicates() {
+    // Test value isn't a feature of cfg_boost Cargo.toml
+    allow_test_feature();
+
     // Test each predefined predicates
     for pred in PREDICATES {
         test_cfg_predicate(pred);
@@ -107,7 +120,7 @@ fn custom_predicates() {
 
     // 2. Set custom predicate in env.
     for pred in &custom_pred {
-        std::env::set_var(format!("{}{}", super::ENV_KEY_PREDICATE, pred.0), pred.1);
+        std::env::set_var(format!("{}{}", ENV_KEY_PREDICATE, pred.0), pred.1);
     }
 
     // 3. Test each custom predicates
@@ -124,6 +137,31 @@ fn error_predicate() {
     test_cfg_predicate(("not_found", "not_found=\"{}\""));
 }
 
+/// Test unknown values of predefined predicates are rejected with suggestions.
+#[test]
+fn unknown_predicate_values() {
+    let settings = Settings::default();
+
+    for (label, suggestion) in [("linx:os", "Did you mean `linux`?"), ("x86-64:ar", "Did you mean `x86_64`?"), ("unwnd:pn", "Did you mean `unwind`?"),
+        ("128:pw", "Did you mean one of `16`, `32`, `64`?")] {
+        match resolve_predicate(&settings, label) {
+            Ok(result) => panic!("unknown_predicate_values test error. Expected error for {}, got {}!", label, result),
+            Err(err) => assert!(err.message(label).contains(suggestion), "Expected `{}` in `{}`!", suggestion, err.message(label)),
+        }
+    }
+}
+
+/// Test values added to predefined predicates with `values` table.
+#[test]
+fn added_predicate_values() {
+    let settings = Settings::parse("[values]\nvn = \"custom_vendor, other_vendor\"").unwrap();
+
+    for label in ["custom_vendor", "other_vendor"] {
+        assert_eq!(resolve_predicate(&settings, &format!("{}:vn", label)).unwrap(), format!("target_vendor = \"{}\"", label));
+    }
+    assert!(resolve_predicate(&settings, "unknown_vendor:vn").is_err());
+}
+
 /// Performance stress test. 
 /// Verify 1 000 000 queries to parse_cfg_predicate. 
 /// Should take less than 5 sec on recent computers.
@@ -132,6 +170,9 @@ fn error_predicate() {
 #[test]
 #[ignore]
 fn stress_performance_predicates() {
+    // Test value isn't a feature of cfg_boost Cargo.toml
+    allow_test_feature();
+
     // Get time started
     let start = Instant::now();
 
@@ -139,11 +180,12 @@ fn stress_performance_predicates() {
         // 1. Pick a predicate
         let predicate = PREDICATES[i & (PREDICATES.len() - 1)];
 
-        // 2. Format predicate label syntax.
-        let label = format!("stress_performance:{}", predicate.0);
+        // 2. Format predicate label syntax with a known value.
+        let value = predicate_test_value(predicate.0);
+        let label = format!("{}:{}", value, predicate.0);
 
         // 3. Set predicate control value expected.
-        let control = String::from(predicate.1.replace(super::PREDICATE_PLACEHOLDER, "stress_performance"));
+        let control = String::from(predicate.1.replace(PREDICATE_PLACEHOLDER, value));
 
         // 4. match result of parse_cfg_predicate function.
         match get_cfg_boost_predicate(label.as_str()){
@@ -171,7 +213,7 @@ fn stress_performance_predicates() {
 ************/
 /// Test a pair of alias, alias value.
 fn test_parse_alias_from_label(alias : (&str, &str)) {
-    match super::get_cfg_boost_alias(alias.0) {
+    match get_cfg_boost_alias(alias.0) {
         Ok(result) => {
             // If result != value, panic!
             if result.ne(alias.1) {
@@ -183,26 +225,41 @@ fn test_parse_alias_from_label(alias : (&str, &str)) {
 }
 
 
-/// Test parse_cfg_predicate from a pair of (PREDICATE, PREDICATE_VALUE)
+/// Test parse_cfg_predicate from a pair of (PREDICATE, PREDICATE_VALUE) with a known value of predicate.
 fn test_cfg_predicate(predicate_tested : (&str,&str)){
+    test_cfg_predicate_value(predicate_tested, predicate_test_value(predicate_tested.0));
+}
+
+/// Add `test_cfg_predicate` to features validated against Cargo.toml.
+fn allow_test_feature() {
+    std::env::set_var(format!("{}{}", ENV_KEY_VALUES, "ft"), "test_cfg_predicate");
+}
+
+/// Get a known value of predicate or `test_cfg_predicate` for features and predicates without known values.
+fn predicate_test_value(predicate : &str) -> &'static str {
+    match PREDICATE_VALUES.iter().find(|values| values.0.eq(predicate)) {
+        Some(values) => values.1[0],
+        None => "test_cfg_predicate",
+    }
+}
+
+/// Test parse_cfg_predicate from a pair of (PREDICATE, PREDICATE_VALUE) with argument value.
+fn test_cfg_predicate_value(predicate_tested : (&str,&str), argument_value : &str){
 
-    // 1. Set predicate argument value
-    const ARGUMENT_VALUE: &str = "test_cfg_predicate";
-    
-    // 2. Format predicate label syntax.
-    let pred = format!("{}:{}", ARGUMENT_VALUE, predicate_tested.0);
+    // 1. Format predicate label syntax.
+    let pred = format!("{}:{}", argument_value, predicate_tested.0);
 
-    // 3. Set predicate control value expected.
-    let control = String::from(predicate_tested.1.replace(super::PREDICATE_PLACEHOLDER, ARGUMENT_VALUE));
+    // 2. Set predicate control value expected.
+    let control = String::from(predicate_tested.1.replace(PREDICATE_PLACEHOLDER, argument_value));
 
-    // 4. match result of parse_cfg_predicate function.
+    // 3. match result of parse_cfg_predicate function.
     match get_cfg_boost_predicate(pred.as_str()){
-        // 4.1. Panic! if result ne control
+        // 3.1. Panic! if result ne control
         Ok(result) => if result.ne(&control){
             panic!("parse_cfg_predicate::{} test error. Expected {}, got {}!", "target_arch_predicate", control, result);
         },
 
-        // 4.2. Error occured, panic!
+        // 3.2. Error occured, panic!
         Err(err) => panic!("{}", err.message(pred.as_str())),
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,38 @@
+use proc_macro2::TokenStream;
+
+use super::{macros::{parse_stream, cfg_meta, doc_tag}, Settings, ParseError};
+
+/// Test predicates parsed from proc_macro2 tokens into cfg meta and documentation tags
+#[test]
+fn macros_cfg_meta() {
+    let settings = Settings::parse("[aliases]\nmacros_gpu = \"vulkan:ft | opengl:ft\"").unwrap();
+
+    // 1. Cfg meta
+    let tree = parse_stream("desktop & !x86_64:ar | macros_gpu".parse::<TokenStream>().unwrap(), &settings).unwrap();
+    assert_eq!(cfg_meta(&tree).to_string(), "cfg (all (any (target_os = \"linux\" , any (windows , target_os = \"macos\")) , \
+        any (not (target_arch = \"x86_64\") , any (feature = \"vulkan\" , feature = \"opengl\"))))");
+
+    // 2. Documentation tag following docs.rs settings
+    let tree = parse_stream("linux".parse::<TokenStream>().unwrap(), &settings).unwrap();
+    assert!(doc_tag(&tree, &settings).is_none());
+    let tag = doc_tag(&tree, &Settings::parse("docsrs = true").unwrap()).unwrap();
+    assert_eq!(tag.to_string(), "cfg_attr (docsrs , doc (cfg (target_os = \"linux\")))");
+    let tag = doc_tag(&tree, &Settings::parse("docsrs = true\ndocsrs_cfg = \"my_docs\"").unwrap()).unwrap();
+    assert_eq!(tag.to_string(), "cfg_attr (my_docs , doc (cfg (target_os = \"linux\")))");
+    assert!(doc_tag(&tree, &Settings::parse("docsrs = true\ndocsrs_mode = \"auto_cfg\"").unwrap()).is_none());
+
+    // 3. Errors
+    let err = parse_stream("linux windows".parse::<TokenStream>().unwrap(), &settings).unwrap_err();
+    assert_eq!(err.error, ParseError::MissingOperator);
+}
+
+/// Test settings loaded from the crate being compiled
+#[test]
+fn macros_settings_load() {
+    use super::Resolver;
+
+    // Optional dependencies of Cargo.toml are implicit features
+    let settings = Settings::load().unwrap();
+    assert_eq!(settings.features(), Some([String::from("proc-macro2")].as_slice()));
+    assert!(parse_stream("proc-macro2:ft".parse::<TokenStream>().unwrap(), &settings).is_ok());
+}
@@ -0,0 +1,141 @@
+use super::{tokenize, to_source, parse, Token, Delimiter, Settings, CfgSet, SyntaxTreeNode, ParseError};
+
+/// Settings of parser tests, with values validation on whatever the environment is.
+fn test_settings() -> Settings {
+    Settings::parse(r#"
+check_values = true
+
+[aliases]
+parser_gpu = "vulkan:ft | opengl:ft"
+parser_nested = "parser_gpu & !wasm"
+
+[predicates]
+pc1 = "custom1 = \"{}\""
+
+[descriptions]
+parser_gpu = "GPU backend"
+"#).unwrap()
+}
+
+/// Test tokenizer and source written from tokens
+#[test]
+fn tokenize_source() {
+    // 1. Tokens with groups and literals
+    let tokens = tokenize("desktop&!(x86_64:ar | 64 : pw | \"a b\":ft)").unwrap();
+    assert_eq!(tokens[..3], [Token::Ident(String::from("desktop")), Token::Punct('&'), Token::Punct('!')]);
+    match &tokens[3] {
+        Token::Group(Delimiter::Parenthesis, group) => {
+            assert_eq!(group[4], Token::Literal(String::from("64")));
+            assert_eq!(group[8], Token::Literal(String::from("\"a b\"")));
+        },
+        token => panic!("Expected group, got {:?}!", token),
+    }
+
+    // 2. Source
+    assert_eq!(to_source(&tokens), "desktop & !(x86_64 : ar | 64 : pw | \"a b\" : ft)");
+    assert_eq!(to_source(&tokenize("all(foo.bar = \"x\",unix)").unwrap()), "all(foo.bar = \"x\", unix)");
+
+    // 3. Unclosed delimiters and quotes
+    for source in ["(linux", "linux)", "(linux]", "\"linux"] {
+        assert_eq!(tokenize(source), Err(ParseError::InvalidTokens), "Expected error for `{}`!", source);
+    }
+}
+
+/// Test syntax tree generated from predicates and written as cfg, note and JSON
+#[test]
+fn parse_tree() {
+    let settings = test_settings();
+
+    // 1. Predicates and their cfg
+    let predicates = [
+        ("linux", "target_os = \"linux\""),
+        ("!x86_64:ar", "not(target_arch = \"x86_64\")"),
+        ("linux & windows | macos", "all(target_os = \"linux\",any(windows,target_os = \"macos\"))"),
+        ("(linux & windows) | macos", "any(all(target_os = \"linux\",windows),target_os = \"macos\")"),
+        ("foo:pc1 & bar:_", "all(custom1 = \"foo\",bar)"),
+        ("parser_nested", "all(any(feature = \"vulkan\",feature = \"opengl\"),not(wasm))"),
+    ];
+    for (predicate, cfg) in predicates {
+        match parse(predicate, &settings) {
+            Ok(tree) => assert_eq!(tree.to_cfg_string(), cfg, "Wrong cfg for `{}`!", predicate),
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    // 2. Nodes
+    let tree = parse("!64:pw & parser_gpu", &settings).unwrap();
+    match tree.as_ref() {
+        SyntaxTreeNode::ALL(left, right) => {
+            assert_eq!(left.as_ref(), &SyntaxTreeNode::NOT(parse("64:pw", &settings).unwrap()));
+            assert!(matches!(right.as_ref(), SyntaxTreeNode::Alias(label, _) if label.eq("parser_gpu")));
+        },
+        node => panic!("Expected ALL node, got {:?}!", node),
+    }
+
+    // 3. Note and JSON
+    assert_eq!(tree.to_note(&settings), "non-**64 bits pointer width** and **GPU backend**");
+    assert!(tree.to_json().starts_with("{\"all\":[{\"not\":{\"leaf\":\"64 : pw\",\"predicate\":\"target_pointer_width = \\\"64\\\"\"}},{\"alias\":\"parser_gpu\""));
+}
+
+/// Test errors of malformed or unresolved predicates, with the tokens they happened in
+#[test]
+fn parse_errors() {
+    let settings = test_settings();
+
+    let errors = [
+        ("linux windows", ParseError::MissingOperator, "linux windows"),
+        ("linux os:os", ParseError::MissingOperator, "linux os : os"),
+        ("linux & ", ParseError::EmptyNode, ""),
+        ("linux + windows", ParseError::InvalidCharacter(String::from("+")), "linux+windows"),
+        ("(linux", ParseError::InvalidTokens, "(linux"),
+        ("parser_unknown", ParseError::AliasNotFound(String::from("parser_unknown")), "parser_unknown"),
+        ("foo:zz", ParseError::InvalidConfigurationPredicate(String::from("zz")), "foo : zz"),
+        ("linx:os", ParseError::UnknownPredicateValue(String::from("os"), vec![String::from("linux")]), "linx : os"),
+    ];
+
+    for (predicate, error, tokens) in errors {
+        match parse(predicate, &settings) {
+            Ok(tree) => panic!("Expected error for `{}`, got {}!", predicate, tree.to_cfg_string()),
+            Err(err) => {
+                assert_eq!(err.error, error, "Wrong error for `{}`!", predicate);
+                assert_eq!(err.tokens, tokens, "Wrong tokens for `{}`!", predicate);
+            },
+        }
+    }
+
+    // Features are validated only if set
+    let settings = test_settings().with_features(vec![String::from("vulkan"), String::from("opengl")]);
+    assert!(parse("parser_gpu", &settings).is_ok());
+    assert_eq!(parse("vulkn:ft", &settings).unwrap_err().to_string(), ParseError::UnknownFeature(vec![String::from("vulkan")]).message("vulkn : ft"));
+
+    // Values and features are validated by default
+    let settings = Settings::default().with_features(vec![String::from("vulkan")]);
+    assert_eq!(parse("vulkn:ft", &settings).unwrap_err().to_string(), ParseError::UnknownFeature(vec![String::from("vulkan")]).message("vulkn : ft"));
+    assert_eq!(parse("linx:os", &settings).unwrap_err().to_string(), ParseError::UnknownPredicateValue(String::from("os"), vec![String::from("linux")]).message("linx : os"));
+}
+
+/// Test evaluation of syntax trees and cfg predicates against a cfg set
+#[test]
+fn eval_cfg_set() {
+    let settings = test_settings();
+    let cfg_set = CfgSet::parse("debug_assertions\ntarget_arch=\"aarch64\"\ntarget_family=\"unix\"\ntarget_os=\"linux\"\ntarget_pointer_width=\"64\"\nunix\nfeature=\"vulkan\"\n");
+
+    // 1. Cfg set
+    assert!(cfg_set.contains("unix", None));
+    assert!(!cfg_set.contains("target_os", None));
+    assert_eq!(cfg_set.values("target_os"), vec!["linux"]);
+
+    // 2. Syntax trees
+    let trees = [("desktop & !x86_64:ar", true), ("parser_nested & 64:pw", true), ("mobile | windows", false), ("!debug", false), ("parser_gpu & !opengl:ft", true)];
+    for (predicate, value) in trees {
+        let tree = parse(predicate, &settings).unwrap();
+        assert_eq!(cfg_set.eval_node(&tree), value, "Wrong value for `{}`!", predicate);
+        assert_eq!(cfg_set.eval_str(&tree.to_cfg_string()), value, "Wrong value for `{}`!", tree.to_cfg_string());
+    }
+
+    // 3. Cfg predicates
+    assert!(cfg_set.eval_str("all(unix, not(target_os = \"macos\"), any(true, false))"));
+    assert!(cfg_set.eval_str("all()"));
+    assert!(!cfg_set.eval_str("any()"));
+    assert!(!cfg_set.eval_str("all(unix"));
+}
@@ -0,0 +1,148 @@
+use std::path::Path;
+
+use crate::{parser::Resolver, toml::TomlDocument};
+
+use super::{Settings, ConfigError, docsrs_cfg_from_manifest};
+
+/// Test configuration files precedence : cfg_boost.toml > [package.metadata.cfg_boost]
+#[test]
+fn config_files_precedence() {
+    // 1. Create crate folder with both files
+    let dir = std::env::temp_dir().join(format!("cfg_boost_config_files_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"foo\"\n\n[package.metadata.cfg_boost]\nautodoc = false\n\n[package.metadata.cfg_boost.aliases]\nfirst = \"manifest:os\"\nsecond = \"manifest:os\"\n").unwrap();
+    std::fs::write(dir.join("cfg_boost.toml"), "[aliases]\nfirst = \"file:os\"\n").unwrap();
+
+    // 2. Load configuration
+    let config = Settings::load_from(Some(&dir), None).unwrap();
+
+    // 3. cfg_boost.toml must override Cargo.toml, which is used when key isn't in cfg_boost.toml
+    assert_eq!(config.get("aliases.first").and_then(|v| v.as_str()), Some("file:os"));
+    assert_eq!(config.get("aliases.second").and_then(|v| v.as_str()), Some("manifest:os"));
+    assert_eq!(config.get("autodoc").and_then(|v| v.as_bool()), Some(false));
+    assert!(config.get("aliases.third").is_none());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test features of Cargo.toml : features table and optional dependencies, including target dependencies, unless referenced with `dep:`.
+#[test]
+fn cargo_features() {
+    // 1. Create crate folder with features
+    let dir = std::env::temp_dir().join(format!("cfg_boost_config_features_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"foo\"\n\n[features]\ndefault = [\"gl\"]\ngl = []\nvulkan = [\"dep:ash\"]\n\n[dependencies]\nash = { version = \"1\", optional = true }\nserde = { version = \"1\", optional = true }\nlog = \"1\"\n\n[target.'cfg(unix)'.dependencies]\nnix = { version = \"1\", optional = true }\n\n[build-dependencies]\ncc = { version = \"1\", optional = true }\n").unwrap();
+
+    // 2. Load configuration
+    let config = Settings::load_from(Some(&dir), None).unwrap();
+    let mut features = config.features().unwrap().to_vec();
+    features.sort();
+
+    // 3. Features and optional dependencies, without build dependencies and dependencies referenced with `dep:`
+    assert_eq!(features, vec!["default", "gl", "nix", "serde", "vulkan"]);
+
+    // 4. No features without Cargo.toml
+    assert!(Settings::load_from(None, None).unwrap().features().is_none());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test workspace configuration inheritance : crate > workspace, only when `inherit = true`
+#[test]
+fn config_workspace_inheritance() {
+    // 1. Create workspace with 2 member crates
+    let dir = std::env::temp_dir().join(format!("cfg_boost_config_workspace_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("inherit")).unwrap();
+    std::fs::create_dir_all(dir.join("standalone")).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), "[workspace]\nmembers = [\"inherit\", \"standalone\"]\n\n[workspace.metadata.cfg_boost.aliases]\nserver = \"workspace:os\"\nembedded = \"workspace:os\"\n").unwrap();
+    std::fs::write(dir.join("inherit").join("Cargo.toml"), "[package]\nname = \"inherit\"\n\n[package.metadata.cfg_boost]\ninherit = true\naliases.embedded = \"crate:os\"\n").unwrap();
+    std::fs::write(dir.join("standalone").join("Cargo.toml"), "[package]\nname = \"standalone\"\n").unwrap();
+
+    // 2. Crate that inherit get workspace aliases, overridden by its own.
+    let config = Settings::load_from(Some(&dir.join("inherit")), None).unwrap();
+    assert_eq!(config.get("aliases.server").and_then(|v| v.as_str()), Some("workspace:os"));
+    assert_eq!(config.get("aliases.embedded").and_then(|v| v.as_str()), Some("crate:os"));
+
+    // 3. Crate that doesn't inherit doesn't get workspace aliases.
+    let config = Settings::load_from(Some(&dir.join("standalone")), None).unwrap();
+    assert!(config.get("aliases.server").is_none());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test explicit configuration file without Cargo and its precedence over cfg_boost.toml
+#[test]
+fn config_explicit_file() {
+    // 1. Create explicit configuration file and crate folder.
+    let dir = std::env::temp_dir().join(format!("cfg_boost_config_explicit_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("hermetic.toml"), "docsrs = true\n\n[aliases]\nfirst = \"hermetic:os\"\n").unwrap();
+    std::fs::write(dir.join("cfg_boost.toml"), "[aliases]\nfirst = \"file:os\"\nsecond = \"file:os\"\n").unwrap();
+
+    // 2. Without manifest directory, only explicit file is used.
+    let config = Settings::load_from(None, Some(&dir.join("hermetic.toml"))).unwrap();
+    assert_eq!(config.get("aliases.first").and_then(|v| v.as_str()), Some("hermetic:os"));
+    assert_eq!(config.get("docsrs").and_then(|v| v.as_bool()), Some(true));
+    assert!(config.get("aliases.second").is_none());
+
+    // 3. With manifest directory, explicit file has precedence.
+    let config = Settings::load_from(Some(&dir), Some(&dir.join("hermetic.toml"))).unwrap();
+    assert_eq!(config.get("aliases.first").and_then(|v| v.as_str()), Some("hermetic:os"));
+    assert_eq!(config.get("aliases.second").and_then(|v| v.as_str()), Some("file:os"));
+
+    // 4. Nothing to load
+    let config = Settings::load_from(None, None).unwrap();
+    assert!(config.get("aliases.first").is_none());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test errors of missing explicit configuration file and malformed cfg_boost.toml
+#[test]
+fn config_errors() {
+    let path = Path::new("/cfg_boost/not/found.toml");
+    assert_eq!(Settings::load_from(None, Some(path)).err(), Some(ConfigError::NotFound(path.to_path_buf())));
+
+    let dir = std::env::temp_dir().join(format!("cfg_boost_config_errors_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("cfg_boost.toml"), "autodoc = true\n[aliases\n").unwrap();
+    assert!(matches!(Settings::load_from(Some(&dir), None), Err(ConfigError::Malformed(path, _)) if path == dir.join("cfg_boost.toml")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Test docs.rs cfg detection from [package.metadata.docs.rs]
+#[test]
+fn docsrs_detection() {
+    // 1. Table in comment only is not detected.
+    let manifest = TomlDocument::parse("[package]\nname = \"foo\"\n# [package.metadata.docs.rs]\n").unwrap();
+    assert_eq!(docsrs_cfg_from_manifest(&manifest, None), None);
+
+    // 2. Table without --cfg is not detected.
+    let manifest = TomlDocument::parse("[package.metadata.docs.rs]\nall-features = true\n").unwrap();
+    assert_eq!(docsrs_cfg_from_manifest(&manifest, None), None);
+
+    // 3. docsrs is preferred, in rustdoc-args or cargo-args
+    let manifest = TomlDocument::parse("[package.metadata.docs.rs]\nrustdoc-args = [\"--cfg\", \"other\", \"--cfg\", \"docsrs\"]\n").unwrap();
+    assert_eq!(docsrs_cfg_from_manifest(&manifest, None), Some(String::from("docsrs")));
+    let manifest = TomlDocument::parse("[package.metadata.docs.rs]\ncargo-args = [\"--cfg=docsrs\"]\n").unwrap();
+    assert_eq!(docsrs_cfg_from_manifest(&manifest, None), Some(String::from("docsrs")));
+
+    // 4. Other cfg name is detected or selected.
+    let manifest = TomlDocument::parse("[package.metadata.docs.rs]\nrustdoc-args = [\"--cfg\", \"my_docs\", \"--cfg\", \"other\"]\n").unwrap();
+    assert_eq!(docsrs_cfg_from_manifest(&manifest, None), Some(String::from("my_docs")));
+    assert_eq!(docsrs_cfg_from_manifest(&manifest, Some("other")), Some(String::from("other")));
+    assert_eq!(docsrs_cfg_from_manifest(&manifest, Some("docsrs")), None);
+}
+
+/// Test docs.rs tags settings : forced on or off, custom cfg name and doc_auto_cfg mode
+#[test]
+fn docsrs_settings() {
+    assert_eq!(Settings::parse("docsrs = true").unwrap().docsrs_cfg(), Some(String::from("docsrs")));
+    assert_eq!(Settings::parse("docsrs = true\ndocsrs_cfg = \"my_docs\"").unwrap().docsrs_cfg(), Some(String::from("my_docs")));
+    assert_eq!(Settings::parse("docsrs = false").unwrap().docsrs_cfg(), None);
+    assert_eq!(Settings::parse("docsrs = true\ndocsrs_mode = \"auto_cfg\"").unwrap().docsrs_cfg(), None);
+
+    // Without Cargo.toml, no tags unless forced.
+    assert_eq!(Settings::parse("").unwrap().docsrs_cfg(), None);
+}
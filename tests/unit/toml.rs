@@ -0,0 +1,86 @@
+use super::{TomlDocument, TomlValue};
+
+/// Test tables, dotted keys and quoted keys
+#[test]
+fn tables_and_keys() {
+    let document = TomlDocument::parse(r#"
+root = "root"
+
+[package.metadata.cfg_boost]
+autodoc = false
+aliases.desktop = "linux:os | windows:_"
+
+[target.'cfg(windows)'.dependencies]
+"quoted-key" = "1.0"
+"#).unwrap();
+
+    assert_eq!(document.get("root"), Some(&TomlValue::String(String::from("root"))));
+    assert_eq!(document.get("package.metadata.cfg_boost.autodoc"), Some(&TomlValue::Boolean(false)));
+    assert_eq!(document.get("package.metadata.cfg_boost.aliases.desktop").and_then(|v| v.as_str()), Some("linux:os | windows:_"));
+    assert_eq!(document.get("target.cfg(windows).dependencies.quoted-key").and_then(|v| v.as_str()), Some("1.0"));
+    assert!(document.contains("package.metadata"));
+    assert!(!document.contains("package.meta"));
+}
+
+/// Test that empty tables are found
+#[test]
+fn empty_table() {
+    let document = TomlDocument::parse("[package]\nname = \"foo\"\n\n[workspace]\n").unwrap();
+
+    assert!(document.contains("workspace"));
+    assert!(document.get("workspace").is_none());
+}
+
+/// Test strings, arrays, inline tables and comments
+#[test]
+fn values() {
+    let document = TomlDocument::parse(r#"
+basic = "a \"quoted\" # not a comment"  # a comment
+literal = 'C:\path'
+multi = """
+first
+second"""
+array = [
+    "--cfg", # comment in array
+    "docsrs",
+]
+number = 42
+dep = { version = "1.0", optional = true }
+"#).unwrap();
+
+    assert_eq!(document.get("basic").and_then(|v| v.as_str()), Some("a \"quoted\" # not a comment"));
+    assert_eq!(document.get("literal").and_then(|v| v.as_str()), Some("C:\\path"));
+    assert_eq!(document.get("multi").and_then(|v| v.as_str()), Some("first\nsecond"));
+    assert_eq!(document.get("array"), Some(&TomlValue::Array(vec![TomlValue::String(String::from("--cfg")), TomlValue::String(String::from("docsrs"))])));
+    assert_eq!(document.get("number"), Some(&TomlValue::Other(String::from("42"))));
+    assert_eq!(document.get("dep.optional").and_then(|v| v.as_bool()), Some(true));
+}
+
+/// Test sub documents and merge precedence
+#[test]
+fn sub_document_and_merge() {
+    let mut low = TomlDocument::parse("[a]\nx = \"low\"\ny = \"low\"").unwrap();
+    let high = TomlDocument::parse("[b.a]\nx = \"high\"").unwrap().sub_document("b");
+
+    low.merge(&high);
+
+    assert_eq!(low.get("a.x").and_then(|v| v.as_str()), Some("high"));
+    assert_eq!(low.get("a.y").and_then(|v| v.as_str()), Some("low"));
+}
+
+/// Test that arrays of tables are skipped
+#[test]
+fn array_of_tables() {
+    let document = TomlDocument::parse("[[bin]]\nname = \"skipped\"\n\n[features]\ndefault = []").unwrap();
+
+    assert!(document.get("bin.name").is_none());
+    assert!(document.get("features.default").is_some());
+}
+
+/// Test malformed content error line
+#[test]
+fn malformed() {
+    assert_eq!(TomlDocument::parse("a = \"ok\"\nb = \"not closed\n").err(), Some(2));
+    assert_eq!(TomlDocument::parse("a = \"ok\" b = 1").err(), Some(1));
+    assert!(TomlDocument::parse("[unclosed\n").is_err());
+}